@@ -73,6 +73,35 @@ impl<'a> Input for NoLimitInput<'a> {
 	}
 }
 
+/// Input counting the calls to `read`.
+struct CountingInput<'a> {
+	input: &'a [u8],
+	reads: usize,
+}
+
+impl<'a> Input for CountingInput<'a> {
+	fn remaining_len(&mut self) -> Result<Option<usize>, Error> {
+		Ok(Some(self.input.len()))
+	}
+
+	fn read(&mut self, into: &mut [u8]) -> Result<(), Error> {
+		self.reads += 1;
+		self.input.read(into)
+	}
+}
+
+fn vec_u8_decode_bulk(b: &mut Bencher) {
+	let encoded = black_box(vec![0xffu8; 1024 * 1024].encode());
+
+	b.iter(|| {
+		let mut input = CountingInput { input: &encoded[..], reads: 0 };
+		let decoded = Vec::<u8>::decode(&mut input).unwrap();
+		// The compact length takes two reads, the bytes a single one whatever their number.
+		assert_eq!(input.reads, 3);
+		decoded
+	});
+}
+
 #[derive(Encode, Decode)]
 enum Event {
 	ComplexEvent(Vec<u8>, u32, i32, u128, i8),
@@ -196,6 +225,7 @@ fn bench_fn(c: &mut Criterion) {
 	c.bench_function("vec_append_with_decode_and_encode", vec_append_with_decode_and_encode);
 	c.bench_function("vec_append_with_encode_append", vec_append_with_encode_append);
 	c.bench_function("array_vec_write_u128", array_vec_write_u128);
+	c.bench_function("vec_u8_decode_bulk", vec_u8_decode_bulk);
 }
 
 fn encode_decode_bitvec_u8(c: &mut Criterion) {
@@ -203,7 +233,7 @@ fn encode_decode_bitvec_u8(c: &mut Criterion) {
 
 	#[cfg(feature = "bit-vec")]
 	c.bench_function_over_inputs("bitvec_u8_encode - BitVec<u8>", |b, &size| {
-		let vec: BitVec<Lsb0, u8> = [true, false]
+		let vec: BitVec<u8, Lsb0> = [true, false]
 			.iter()
 			.cloned()
 			.cycle()
@@ -216,7 +246,7 @@ fn encode_decode_bitvec_u8(c: &mut Criterion) {
 
 	#[cfg(feature = "bit-vec")]
 	c.bench_function_over_inputs("bitvec_u8_decode - BitVec<u8>", |b, &size| {
-		let vec: BitVec<Lsb0, u8> = [true, false]
+		let vec: BitVec<u8, Lsb0> = [true, false]
 			.iter()
 			.cloned()
 			.cycle()
@@ -227,7 +257,7 @@ fn encode_decode_bitvec_u8(c: &mut Criterion) {
 
 		let vec = black_box(vec);
 		b.iter(|| {
-			let _: BitVec<Lsb0, u8> = Decode::decode(&mut &vec[..]).unwrap();
+			let _: BitVec<u8, Lsb0> = Decode::decode(&mut &vec[..]).unwrap();
 		})
 	}, vec![1, 2, 5, 32, 1024]);
 }
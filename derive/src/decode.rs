@@ -49,14 +49,11 @@ pub fn quote(
 		Data::Enum(ref data) => {
 			let data_variants = || data.variants.iter().filter(|variant| !utils::should_skip(&variant.attrs));
 
-			if data_variants().count() > 256 {
-				return Error::new(
-					data.variants.span(),
-					"Currently only enums with at most 256 variants are encodable."
-				).to_compile_error();
-			}
+			let (index_type, index_size) = utils::variant_index_type(data_variants().count());
 
-			let recurse = data_variants().enumerate().map(|(i, v)| {
+			// The `unknown` variant holding a raw discriminant is only decoded by the fallback.
+			let variants = data_variants().enumerate().filter(|(_, v)| !utils::captures_discriminant(v));
+			let recurse = variants.map(|(i, v)| {
 				let name = &v.ident;
 				let index = utils::variant_index(v, i);
 
@@ -68,7 +65,7 @@ pub fn quote(
 				);
 
 				quote_spanned! { v.span() =>
-					__codec_x_edqy if __codec_x_edqy == #index as ::core::primitive::u8 => {
+					__codec_x_edqy if __codec_x_edqy == #index as #index_type => {
 						#create
 					},
 				}
@@ -82,12 +79,44 @@ pub fn quote(
 				"Could not decode `{}`, variant doesn't exist",
 				type_name,
 			);
+			let fallback = match data_variants().find(|v| utils::is_unknown_variant(&v.attrs)) {
+				Some(v) if utils::captures_discriminant(v) => {
+					let name = &v.ident;
+					quote_spanned! { v.span() =>
+						__codec_x_edqy => ::core::result::Result::Ok(
+							#type_name #type_generics :: #name (__codec_x_edqy)
+						),
+					}
+				},
+				Some(v) => {
+					let name = &v.ident;
+					quote_spanned! { v.span() =>
+						_ => ::core::result::Result::Ok(#type_name #type_generics :: #name),
+					}
+				},
+				None => match data_variants().find_map(|v| utils::get_unknown_with(&v.attrs)) {
+					// Nothing is read after the unknown index, the caller skips the payload if it can.
+					Some(expr) => quote_spanned! { expr.span() =>
+						_ => ::core::result::Result::Ok(#expr),
+					},
+					None => quote! {
+						_ => ::core::result::Result::Err(#invalid_variant_err_msg.into()),
+					},
+				},
+			};
+
+			let read_index = if index_size == 1 {
+				quote! { #input.read_byte() }
+			} else {
+				quote! { <#index_type as _parity_scale_codec::Decode>::decode(#input) }
+			};
+
 			quote! {
-				match #input.read_byte()
+				match #read_index
 					.map_err(|e| e.chain(#read_byte_err_msg))?
 				{
 					#( #recurse )*
-					_ => ::core::result::Result::Err(#invalid_variant_err_msg.into()),
+					#fallback
 				}
 			}
 
@@ -98,15 +127,16 @@ pub fn quote(
 
 fn create_decode_expr(field: &Field, name: &str, input: &TokenStream) -> TokenStream {
 	let encoded_as = utils::get_encoded_as_type(field);
+	let with = utils::get_with_module(field);
 	let compact = utils::is_compact(field);
 	let skip = utils::should_skip(&field.attrs);
 
 	let res = quote!(__codec_res_edqy);
 
-	if encoded_as.is_some() as u8 + compact as u8 + skip as u8 > 1 {
+	if encoded_as.is_some() as u8 + with.is_some() as u8 + compact as u8 + skip as u8 > 1 {
 		return Error::new(
 			field.span(),
-			"`encoded_as`, `compact` and `skip` can only be used one at a time!"
+			"`encoded_as`, `with`, `compact` and `skip` can only be used one at a time!"
 		).to_compile_error();
 	}
 
@@ -135,6 +165,16 @@ fn create_decode_expr(field: &Field, name: &str, input: &TokenStream) -> TokenSt
 				}
 			}
 		}
+	} else if let Some(with) = with {
+		quote_spanned! { field.span() =>
+			{
+				let #res = #with::decode(#input);
+				match #res {
+					::core::result::Result::Err(e) => return ::core::result::Result::Err(e.chain(#err_msg)),
+					::core::result::Result::Ok(#res) => #res,
+				}
+			}
+		}
 	} else if skip {
 		quote_spanned! { field.span() => ::core::default::Default::default() }
 	} else {
@@ -157,7 +197,33 @@ fn create_instance(
 	input: &TokenStream,
 	fields: &Fields
 ) -> TokenStream {
-	match *fields {
+	// With `#[codec(pad = $int)]` fields, the offset from the first field is needed to know the
+	// padding, so the fields are decoded through an input counting the read bytes.
+	let aligned = fields.iter().any(|f| utils::get_pad(f).is_some());
+	let aligned_input = quote!(__codec_aligned_input_edqy);
+	let field_input = if aligned { quote!(&mut #aligned_input) } else { input.clone() };
+
+	let decode_field = |f: &Field, field_name: &str| {
+		let decode = create_decode_expr(f, field_name, &field_input);
+
+		match utils::get_pad(f) {
+			Some(pad) => {
+				let err_msg = format!("Could not decode `{}`", field_name);
+				quote_spanned! { f.span() =>
+					{
+						let __codec_field_edqy = #decode;
+						if let ::core::result::Result::Err(e) = #aligned_input.align_to(#pad) {
+							return ::core::result::Result::Err(e.chain(#err_msg));
+						}
+						__codec_field_edqy
+					}
+				}
+			},
+			None => decode,
+		}
+	};
+
+	let instance = match *fields {
 		Fields::Named(ref fields) => {
 			let recurse = fields.named.iter().map(|f| {
 				let name_ident = &f.ident;
@@ -165,7 +231,7 @@ fn create_instance(
 					Some(a) => format!("{}::{}", name_str, a),
 					None => format!("{}", name_str), // Should never happen, fields are named.
 				};
-				let decode = create_decode_expr(f, &field_name, input);
+				let decode = decode_field(f, &field_name);
 
 				quote_spanned! { f.span() =>
 					#name_ident: #decode
@@ -182,7 +248,7 @@ fn create_instance(
 			let recurse = fields.unnamed.iter().enumerate().map(|(i, f) | {
 				let field_name = format!("{}.{}", name_str, i);
 
-				create_decode_expr(f, &field_name, input)
+				decode_field(f, &field_name)
 			});
 
 			quote_spanned! { fields.span() =>
@@ -196,5 +262,16 @@ fn create_instance(
 				::core::result::Result::Ok(#name)
 			}
 		},
+	};
+
+	if aligned {
+		quote! {
+			{
+				let mut #aligned_input = _parity_scale_codec::AlignedInput::new(#input);
+				#instance
+			}
+		}
+	} else {
+		instance
 	}
 }
@@ -74,6 +74,10 @@ fn encode_single_field(
 	let i_self = quote! { self };
 
 	quote_spanned! { field.span() =>
+			fn size_hint(&#i_self) -> usize {
+				_parity_scale_codec::Encode::size_hint(&#final_field_variable)
+			}
+
 			fn encode_to<__CodecOutputEdqy: _parity_scale_codec::Output + ?Sized>(
 				&#i_self,
 				__codec_dest_edqy: &mut __CodecOutputEdqy
@@ -98,22 +102,29 @@ fn encode_fields<F>(
 ) -> TokenStream where
 	F: Fn(usize, &Option<Ident>) -> TokenStream,
 {
+	// With `#[codec(pad = $int)]` fields, the offset from the first field is needed to know the
+	// padding, so the fields are encoded through an output counting the written bytes.
+	let aligned = fields.iter().any(|f| utils::get_pad(f).is_some());
+	let aligned_dest = quote!(__codec_aligned_dest_edqy);
+	let field_dest = if aligned { quote!(&mut #aligned_dest) } else { dest.clone() };
+
 	let recurse = fields.iter().enumerate().map(|(i, f)| {
 		let field = field_name(i, &f.ident);
 		let encoded_as = utils::get_encoded_as_type(f);
+		let with = utils::get_with_module(f);
 		let compact = utils::is_compact(f);
 		let skip = utils::should_skip(&f.attrs);
 
-		if encoded_as.is_some() as u8 + compact as u8 + skip as u8 > 1 {
+		if encoded_as.is_some() as u8 + with.is_some() as u8 + compact as u8 + skip as u8 > 1 {
 			return Error::new(
 				f.span(),
-				"`encoded_as`, `compact` and `skip` can only be used one at a time!"
+				"`encoded_as`, `with`, `compact` and `skip` can only be used one at a time!"
 			).to_compile_error();
 		}
 
 		// Based on the seen attribute, we generate the code that encodes the field.
 		// We call `push` from the `Output` trait on `dest`.
-		if compact {
+		let encode = if compact {
 			let field_type = &f.ty;
 			quote_spanned! {
 				f.span() => {
@@ -122,7 +133,7 @@ fn encode_fields<F>(
 							<#field_type as _parity_scale_codec::HasCompact>::Type as
 							_parity_scale_codec::EncodeAsRef<'_, #field_type>
 						>::RefType::from(#field),
-						#dest,
+						#field_dest,
 					);
 				}
 			}
@@ -135,28 +146,51 @@ fn encode_fields<F>(
 							#encoded_as as
 							_parity_scale_codec::EncodeAsRef<'_, #field_type>
 						>::RefType::from(#field),
-						#dest,
+						#field_dest,
 					);
 				}
 			}
+		} else if let Some(with) = with {
+			quote_spanned! { f.span() =>
+					#with::encode(#field, #field_dest);
+			}
 		} else if skip {
 			quote! {
 				let _ = #field;
 			}
 		} else {
 			quote_spanned! { f.span() =>
-					_parity_scale_codec::Encode::encode_to(#field, #dest);
+					_parity_scale_codec::Encode::encode_to(#field, #field_dest);
 			}
+		};
+
+		match utils::get_pad(f) {
+			Some(pad) => quote! {
+				#encode
+				#aligned_dest.align_to(#pad);
+			},
+			None => encode,
 		}
 	});
 
-	quote! {
-		#( #recurse )*
+	if aligned {
+		quote! {
+			let mut #aligned_dest = _parity_scale_codec::AlignedOutput::new(#dest);
+			#( #recurse )*
+		}
+	} else {
+		quote! {
+			#( #recurse )*
+		}
 	}
 }
 
 fn try_impl_encode_single_field_optimisation(data: &Data) -> Option<TokenStream> {
 	match *data {
+		// Padding must be written after the field, so it can't be encoded as the field alone.
+		Data::Struct(ref data) if data.fields.iter().any(|f| utils::get_pad(f).is_some()) => None,
+		// A field encoded by a custom module doesn't implement `Encode` itself.
+		Data::Struct(ref data) if data.fields.iter().any(|f| utils::get_with_module(f).is_some()) => None,
 		Data::Struct(ref data) => {
 			match data.fields {
 				Fields::Named(ref fields) if utils::filter_skip_named(fields).count() == 1 => {
@@ -208,21 +242,25 @@ fn impl_encode(data: &Data, type_name: &Ident) -> TokenStream {
 		Data::Enum(ref data) => {
 			let data_variants = || data.variants.iter().filter(|variant| !utils::should_skip(&variant.attrs));
 
-			if data_variants().count() > 256 {
-				return Error::new(
-					data.variants.span(),
-					"Currently only enums with at most 256 variants are encodable."
-				).to_compile_error();
-			}
-
-			// If the enum has no variants, we don't need to encode anything.
-			if data_variants().count() == 0 {
-				return quote!();
-			}
+			let (index_type, index_size) = utils::variant_index_type(data_variants().count());
 
 			let recurse = data_variants().enumerate().map(|(i, f)| {
 				let name = &f.ident;
 				let index = utils::variant_index(f, i);
+				let encode_index = if index_size == 1 {
+					quote! { #dest.push_byte(#index as ::core::primitive::u8); }
+				} else {
+					quote! { _parity_scale_codec::Encode::encode_to(&(#index as #index_type), #dest); }
+				};
+
+				// The `unknown` variant holding a raw discriminant is encoded as just that byte.
+				if utils::captures_discriminant(f) {
+					return quote_spanned! { f.span() =>
+						#type_name :: #name ( ref __codec_discriminant_edqy ) => {
+							#dest.push_byte(*__codec_discriminant_edqy);
+						}
+					}
+				}
 
 				match f.fields {
 					Fields::Named(ref fields) => {
@@ -240,7 +278,7 @@ fn impl_encode(data: &Data, type_name: &Ident) -> TokenStream {
 
 						quote_spanned! { f.span() =>
 							#type_name :: #name { #( ref #names, )* } => {
-								#dest.push_byte(#index as ::core::primitive::u8);
+								#encode_index
 								#encode_fields
 							}
 						}
@@ -265,7 +303,7 @@ fn impl_encode(data: &Data, type_name: &Ident) -> TokenStream {
 
 						quote_spanned! { f.span() =>
 							#type_name :: #name ( #( ref #names, )* ) => {
-								#dest.push_byte(#index as ::core::primitive::u8);
+								#encode_index
 								#encode_fields
 							}
 						}
@@ -273,17 +311,24 @@ fn impl_encode(data: &Data, type_name: &Ident) -> TokenStream {
 					Fields::Unit => {
 						quote_spanned! { f.span() =>
 							#type_name :: #name => {
-								#dest.push_byte(#index as ::core::primitive::u8);
+								#encode_index
 							}
 						}
 					},
 				}
 			});
 
-			quote! {
-				match *#self_ {
-					#( #recurse )*,
-					_ => (),
+			// An enum without variants can't be instantiated, so there is nothing to encode.
+			if data.variants.is_empty() {
+				quote! {
+					match *#self_ {}
+				}
+			} else {
+				quote! {
+					match *#self_ {
+						#( #recurse )*
+						_ => (),
+					}
 				}
 			}
 		},
@@ -311,6 +356,54 @@ pub fn quote(data: &Data, type_name: &Ident) -> TokenStream {
 	}
 }
 
+/// The items of a `#[codec(variant_names)]` enum, mapping its variants to their index and name.
+pub fn quote_variant_names(data: &Data, type_name: &Ident) -> TokenStream {
+	let data = match *data {
+		Data::Enum(ref data) => data,
+		_ => return Error::new(
+			Span::call_site(),
+			"Internal error: `variant_names` must have been checked to be on an enum",
+		).to_compile_error(),
+	};
+
+	// A variant storing the raw index is encoded with that index, not with its position.
+	let listed = || data.variants.iter().enumerate().filter(|(_, v)| !utils::captures_discriminant(v));
+	let names = listed().map(|(_, v)| v.ident.to_string());
+	let indices = listed().map(|(i, v)| utils::variant_index(v, i));
+	let arms = data.variants.iter().enumerate().map(|(i, v)| {
+		let name = &v.ident;
+		let index = utils::variant_index(v, i);
+
+		if utils::captures_discriminant(v) {
+			quote_spanned! { v.span() =>
+				#type_name :: #name ( ref __codec_discriminant_edqy ) => *__codec_discriminant_edqy as u32,
+			}
+		} else {
+			match v.fields {
+				Fields::Named(_) => quote_spanned! { v.span() => #type_name :: #name { .. } => #index as u32, },
+				Fields::Unnamed(_) => quote_spanned! { v.span() => #type_name :: #name ( .. ) => #index as u32, },
+				Fields::Unit => quote_spanned! { v.span() => #type_name :: #name => #index as u32, },
+			}
+		}
+	});
+
+	quote! {
+		/// The names of the variants, in declaration order, except an `unknown` variant storing the
+		/// raw index.
+		pub const VARIANT_NAMES: &'static [&'static ::core::primitive::str] = &[ #( #names, )* ];
+
+		/// The indices the variants are encoded with, in the order of `VARIANT_NAMES`.
+		pub const VARIANT_INDICES: &'static [::core::primitive::u32] = &[ #( #indices as u32, )* ];
+
+		/// The index `self` is encoded with.
+		pub fn variant_index(&self) -> ::core::primitive::u32 {
+			match *self {
+				#( #arms )*
+			}
+		}
+	}
+}
+
 pub fn stringify(id: u8) -> [u8; 2] {
 	const CHARS: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
 	let len = CHARS.len() as u8;
@@ -37,16 +37,19 @@ mod utils;
 mod trait_bounds;
 
 /// Include the `parity-scale-codec` crate under a known name (`_parity_scale_codec`).
+///
+/// This is a `use` and not an `extern crate` item, as the compiler would otherwise name the items
+/// of the crate `_::_parity_scale_codec::...` in its diagnostics.
 fn include_parity_scale_codec_crate() -> proc_macro2::TokenStream {
 	// This "hack" is required for the tests.
 	if std::env::var("CARGO_PKG_NAME").unwrap() == "parity-scale-codec" {
-		quote!( extern crate parity_scale_codec as _parity_scale_codec; )
+		quote!( use parity_scale_codec as _parity_scale_codec; )
 	} else {
 		match crate_name("parity-scale-codec") {
-			Ok(FoundCrate::Itself) => quote!( extern crate parity_scale_codec as _parity_scale_codec; ),
+			Ok(FoundCrate::Itself) => quote!( use parity_scale_codec as _parity_scale_codec; ),
 			Ok(FoundCrate::Name(parity_codec_crate)) => {
 				let ident = Ident::new(&parity_codec_crate, Span::call_site());
-				quote!( extern crate #ident as _parity_scale_codec; )
+				quote!( use #ident as _parity_scale_codec; )
 			},
 			Err(e) => Error::new(Span::call_site(), &e).to_compile_error(),
 		}
@@ -61,7 +64,7 @@ fn wrap_with_dummy_const(input: DeriveInput, impl_block: proc_macro2::TokenStrea
 	let generated = quote! {
 		const _: () = {
 			#[allow(unknown_lints)]
-			#[cfg_attr(feature = "cargo-clippy", allow(useless_attribute))]
+			#[allow(clippy::useless_attribute)]
 			#[allow(rust_2018_idioms)]
 			#parity_codec_crate
 			#(#attrs)*
@@ -86,9 +89,32 @@ fn wrap_with_dummy_const(input: DeriveInput, impl_block: proc_macro2::TokenStrea
 ///   type must implement `parity_scale_codec::EncodeAsRef<'_, $FieldType>` with $FieldType the
 ///   type of the field with the attribute. This is intended to be used for types implementing
 ///   `HasCompact` as shown in the example.
+/// * `#[codec(with = "$module")]`: the field is encoded and decoded by the free functions
+///   `$module::encode(&$FieldType, &mut impl Output)` and
+///   `$module::decode(&mut impl Input) -> Result<$FieldType, Error>`, for types which don't
+///   implement the traits or need another encoding. Deriving `MaxEncodedLen` also requires
+///   `$module::max_encoded_len() -> usize`.
+/// * `#[codec(pad = $int)]`: the field is followed by zero bytes until the offset from the first
+///   field is a multiple of $int. Decoding skips these bytes without checking them. This is not
+///   SCALE, it is intended to interoperate with formats aligning their fields.
 /// * `#[codec(encode_bound(T: Encode))]`: a custom where bound that will be used when deriving the `Encode` trait.
 /// * `#[codec(decode_bound(T: Encode))]`: a custom where bound that will be used when deriving the `Decode` trait.
 ///
+/// A struct with exactly one non skipped field can have the top attribute
+/// `#[codec(transparent)]`, guaranteeing that it is encoded exactly like this field. The field
+/// can't be `compact`, `encoded_as`, `with` or `pad`. `size_hint` and `MaxEncodedLen` are forwarded to the
+/// field type, and the struct also implements `EncodeLike<$FieldType>`.
+///
+/// ```
+/// # use parity_scale_codec_derive::Encode;
+/// # use parity_scale_codec::Encode as _;
+/// #[derive(Encode)]
+/// #[codec(transparent)]
+/// struct AccountId([u8; 4]);
+///
+/// assert_eq!(AccountId([1, 2, 3, 4]).encode(), [1u8, 2, 3, 4].encode());
+/// ```
+///
 /// ```
 /// # use parity_scale_codec_derive::Encode;
 /// # use parity_scale_codec::{Encode as _, HasCompact};
@@ -105,7 +131,11 @@ fn wrap_with_dummy_const(input: DeriveInput, impl_block: proc_macro2::TokenStrea
 ///
 /// # Enum
 ///
-/// The variable is encoded with one byte for the variant and then the variant struct encoding.
+/// The variable is encoded with the variant number and then the variant struct encoding.
+/// The variant number is encoded as a `u8` for enums with at most 256 variants, as a `u16` for
+/// at most 65536 variants and as a `u32` otherwise, skipped variants not counted. Adding the
+/// 257th variant to an enum thus changes the encoding of all of its variants, and the
+/// `#[codec(unknown)]` variant can only store the raw index of enums with a `u8` variant number.
 /// The variant number is:
 /// * if variant has attribute: `#[codec(index = "$n")]` then n
 /// * else if variant has discrimant (like 3 in `enum T { A = 3 }`) then the discrimant.
@@ -114,11 +144,46 @@ fn wrap_with_dummy_const(input: DeriveInput, impl_block: proc_macro2::TokenStrea
 /// index.
 ///
 /// variant attributes:
-/// * `#[codec(skip)]`: the variant is not encoded.
-/// * `#[codec(index = "$n")]`: override variant index.
+/// * `#[codec(skip)]`: the variant is not encoded, and decoding never produces it. Skipped
+///   variants don't take an index, so skipping a variant shifts the index of the following
+///   variants without explicit index. To keep an existing encoding when deprecating a variant,
+///   give explicit indices to the other variants; decoding the former index then fails.
+/// * `#[codec(index = "$n")]`: override variant index. It applies to unit, tuple and struct
+///   variants alike, the fields being encoded after the index in their declaration order.
+/// * `#[codec(unknown)]`: decode any unrecognized variant index into this variant instead of
+///   failing. A unit variant is encoded with its own index. A variant with a single `u8` field
+///   stores the raw index, which it is encoded as; it should not hold the index of another variant.
+/// * `#[codec(unknown_with = "$expr")]`: decode any unrecognized variant index as the value of
+///   `$expr`, of the enum type, without reading anything after the index. The variant itself is
+///   encoded and decoded like any other. Skipping the payload of the unknown variant is left to
+///   the caller, e.g. by decoding the enum from a length delimited frame. Only one variant can be
+///   marked `unknown` or `unknown_with`.
 ///
 /// field attributes: same as struct fields attributes.
 ///
+/// An enum without skipped variants can have the top attribute `#[codec(variant_names)]` to get
+/// the inherent items `VARIANT_NAMES`, the names of its variants in declaration order,
+/// `VARIANT_INDICES`, the index of each of these variants, and `variant_index(&self) -> u32`, the
+/// index `self` is encoded with. This is meant to map the encoded indices to names, e.g. to
+/// convert the encoding to another format; the encoding itself is unchanged. A
+/// `#[codec(unknown)]` variant storing the raw index has no index of its own, so it is not listed
+/// in `VARIANT_NAMES` and `VARIANT_INDICES`: the indices missing from them decode to it.
+///
+/// ```
+/// # use parity_scale_codec_derive::Encode;
+/// #[derive(Encode)]
+/// #[codec(variant_names)]
+/// enum Event {
+///     Created { id: u32 },
+///     #[codec(index = 5)]
+///     Deleted(u32),
+/// }
+///
+/// assert_eq!(Event::VARIANT_NAMES, &["Created", "Deleted"]);
+/// assert_eq!(Event::VARIANT_INDICES, &[0, 5]);
+/// assert_eq!(Event::Deleted(1).variant_index(), 5);
+/// ```
+///
 /// ```
 /// # use parity_scale_codec_derive::Encode;
 /// # use parity_scale_codec::Encode as _;
@@ -133,7 +198,7 @@ fn wrap_with_dummy_const(input: DeriveInput, impl_block: proc_macro2::TokenStrea
 /// }
 ///
 /// assert_eq!(EnumType::A.encode(), vec![15]);
-/// assert_eq!(EnumType::B.encode(), vec![]);
+/// assert_eq!(EnumType::B.encode(), Vec::<u8>::new());
 /// assert_eq!(EnumType::C.encode(), vec![3]);
 /// assert_eq!(EnumType::D.encode(), vec![2]);
 /// ```
@@ -148,6 +213,20 @@ pub fn encode_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream
 		return e.to_compile_error().into();
 	}
 
+	// The inherent items don't depend on the encoding, so they don't get the `Encode` bounds.
+	let variant_names = if utils::has_variant_names(&input.attrs) {
+		let name = &input.ident;
+		let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+		let items = encode::quote_variant_names(&input.data, name);
+		Some(quote! {
+			impl #impl_generics #name #ty_generics #where_clause {
+				#items
+			}
+		})
+	} else {
+		None
+	};
+
 	if let Some(custom_bound) = utils::custom_encode_trait_bound(&input.attrs) {
 		input.generics.make_where_clause().predicates.extend(custom_bound);
 	} else if let Err(e) = trait_bounds::add(
@@ -166,12 +245,22 @@ pub fn encode_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream
 
 	let encode_impl = encode::quote(&input.data, name);
 
+	// A transparent type is encoded exactly like its field, so it can be used in its place.
+	let transparent_encode_like = utils::transparent_field_type(&input.attrs, &input.data).map(|field_type| quote! {
+		impl #impl_generics _parity_scale_codec::EncodeLike<#field_type>
+			for #name #ty_generics #where_clause {}
+	});
+
 	let impl_block = quote! {
 		impl #impl_generics _parity_scale_codec::Encode for #name #ty_generics #where_clause {
 			#encode_impl
 		}
 
 		impl #impl_generics _parity_scale_codec::EncodeLike for #name #ty_generics #where_clause {}
+
+		#transparent_encode_like
+
+		#variant_names
 	};
 
 	wrap_with_dummy_const(input, impl_block)
@@ -209,7 +298,23 @@ pub fn decode_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream
 	let ty_gen_turbofish = ty_generics.as_turbofish();
 
 	let input_ = quote!(__codec_input_edqy);
-	let decoding = decode::quote(&input.data, name, &quote!(#ty_gen_turbofish), &input_);
+	let mut decoding = decode::quote(&input.data, name, &quote!(#ty_gen_turbofish), &input_);
+
+	if let Some(validate) = utils::get_validate_fn(&input.attrs) {
+		let err_msg = format!("Could not validate `{}`", name);
+		decoding = quote! {
+			let __codec_res_edqy: ::core::result::Result<Self, _parity_scale_codec::Error> = {
+				#decoding
+			};
+			let __codec_value_edqy = __codec_res_edqy?;
+			match #validate(&__codec_value_edqy) {
+				::core::result::Result::Ok(()) => ::core::result::Result::Ok(__codec_value_edqy),
+				::core::result::Result::Err(e) => ::core::result::Result::Err(
+					_parity_scale_codec::Error::from(e).chain(#err_msg)
+				),
+			}
+		};
+	}
 
 	let impl_block = quote! {
 		impl #impl_generics _parity_scale_codec::Decode for #name #ty_generics #where_clause {
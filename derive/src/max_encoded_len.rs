@@ -15,10 +15,11 @@
 
 #![cfg(feature = "max-encoded-len")]
 
+use crate::trait_bounds;
 use crate::utils::{self, codec_crate_path, custom_mel_trait_bound};
 use quote::{quote, quote_spanned};
 use syn::{
-	Data, DeriveInput, Fields, GenericParam, Generics, TraitBound, Type, TypeParamBound,
+	Data, DeriveInput, Fields, GenericParam, Generics, Ident, Path, TraitBound, TypeParamBound,
 	parse_quote, spanned::Spanned,
 };
 
@@ -34,15 +35,23 @@ pub fn derive_max_encoded_len(input: proc_macro::TokenStream) -> proc_macro::Tok
 		Err(e) => return e.to_compile_error().into(),
 	};
 
+	let crate_path = match codec_crate_path(&input.attrs) {
+		Ok(crate_path) => crate_path,
+		Err(e) => return e.to_compile_error().into(),
+	};
+
 	let name = &input.ident;
 	let generics = if let Some(custom_bound) = custom_mel_trait_bound(&input.attrs) {
 		add_custom_trait_bounds(input.generics, custom_bound)
 	} else {
-		add_trait_bounds(input.generics, mel_trait.clone())
+		add_trait_bounds(input.generics, &input.data, mel_trait.clone())
 	};
 	let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
-	let data_expr = data_length_expr(&input.data);
+	let data_expr = match utils::transparent_field_type(&input.attrs, &input.data) {
+		Some(ty) => quote_spanned! { ty.span() => <#ty>::max_encoded_len() },
+		None => data_length_expr(&input.data, &crate_path, &type_params(&generics)),
+	};
 
 	quote::quote!(
 		const _: () = {
@@ -61,16 +70,40 @@ fn max_encoded_len_trait(input: &DeriveInput) -> syn::Result<TraitBound> {
 	Ok(parse_quote!(#mel::MaxEncodedLen))
 }
 
-// Add a bound `T: MaxEncodedLen` to every type parameter T.
-fn add_trait_bounds(mut generics: Generics, mel_trait: TraitBound) -> Generics {
+// Add a bound `T: MaxEncodedLen` to every type parameter T used by a field counted in the
+// maximum length, see `is_counted`.
+fn add_trait_bounds(mut generics: Generics, data: &Data, mel_trait: TraitBound) -> Generics {
+	// Unions are rejected by `data_length_expr`, bound every parameter meanwhile.
+	let counted_types = trait_bounds::collect_types(data, is_counted).ok();
 	for param in &mut generics.params {
 		if let GenericParam::Type(ref mut type_param) = *param {
-			type_param.bounds.push(TypeParamBound::Trait(mel_trait.clone()));
+			let ident = [type_param.ident.clone()];
+			let is_used = match counted_types {
+				Some(ref types) => types.iter().any(|ty| trait_bounds::type_contain_idents(ty, &ident)),
+				None => true,
+			};
+			if is_used {
+				type_param.bounds.push(TypeParamBound::Trait(mel_trait.clone()));
+			}
 		}
 	}
 	generics
 }
 
+// Whether the field is counted in the maximum length. Skipped fields are not encoded, and
+// `PhantomData` fields are encoded as nothing, whatever their type parameter. The length of a
+// `#[codec(with = "$module")]` field is given by `$module`.
+fn is_counted(field: &syn::Field) -> bool {
+	!utils::should_skip(&field.attrs)
+		&& !utils::is_phantom_data(&field.ty)
+		&& utils::get_with_module(field).is_none()
+}
+
+// The idents of the type parameters of `generics`.
+fn type_params(generics: &Generics) -> Vec<Ident> {
+	generics.type_params().map(|param| param.ident.clone()).collect()
+}
+
 // Add custom trait bounds to the type parameters as specified by the user.
 fn add_custom_trait_bounds(mut generics: Generics, custom_bound: utils::TraitBounds) -> Generics {
 	generics.make_where_clause().predicates.extend(custom_bound);
@@ -78,25 +111,66 @@ fn add_custom_trait_bounds(mut generics: Generics, custom_bound: utils::TraitBou
 }
 
 /// generate an expression to sum up the max encoded length from several fields
-fn fields_length_expr(fields: &Fields) -> proc_macro2::TokenStream {
-	let type_iter: Box<dyn Iterator<Item = &Type>> = match fields {
-		Fields::Named(ref fields) => Box::new(fields.named.iter().map(|field| &field.ty)),
-		Fields::Unnamed(ref fields) => Box::new(fields.unnamed.iter().map(|field| &field.ty)),
-		Fields::Unit => Box::new(std::iter::empty()),
-	};
+fn fields_length_expr(fields: &Fields, crate_path: &Path, type_params: &[Ident]) -> proc_macro2::TokenStream {
 	// expands to an expression like
 	//
 	//   0
-	//     .saturating_add(<type of first field>::max_encoded_len())
-	//     .saturating_add(<type of second field>::max_encoded_len())
+	//     .saturating_add(<type of first field as MaxEncodedLenField>::field_max_encoded_len())
+	//     .saturating_add(<type of second field as MaxEncodedLen>::max_encoded_len())
 	//
 	// We match the span of each field to the span of the corresponding
 	// `max_encoded_len` call. This way, if one field's type doesn't implement
 	// `MaxEncodedLen`, the compiler's error message will underline which field
-	// caused the issue.
-	let expansion = type_iter.map(|ty| {
-		quote_spanned! {
-			ty.span() => .saturating_add(<#ty>::max_encoded_len())
+	// caused the issue. The trait is named explicitly, so the error is the unsatisfied
+	// bound instead of a missing associated function. A field type without type parameters
+	// goes through `MaxEncodedLenField`, so the error names the type without listing every
+	// type implementing `MaxEncodedLen`. A field type using a type parameter goes through
+	// `MaxEncodedLen` itself, so that the compiler suggests to bound the parameter with it.
+	//
+	// A `#[codec(compact)]` field is encoded as its compact type, so its length is the one of
+	// `<type as HasCompact>::Type`.
+	//
+	// A `#[codec(with = "$module")]` field is encoded by `$module`, which then provides its
+	// `max_encoded_len` function.
+	//
+	// A `#[codec(pad = $int)]` field is followed by at most `$int - 1` bytes of padding.
+	//
+	// Skipped and `PhantomData` fields are not counted.
+	let counted = fields.iter().filter(|field| {
+		!utils::should_skip(&field.attrs) && !utils::is_phantom_data(&field.ty)
+	});
+	let expansion = counted.map(|field| {
+		let ty = &field.ty;
+		let is_generic = trait_bounds::type_contain_idents(ty, type_params);
+		let length = if let Some(with) = utils::get_with_module(field) {
+			quote_spanned! {
+				ty.span() => .saturating_add(#with::max_encoded_len())
+			}
+		} else {
+			let counted_ty = if utils::is_compact(field) {
+				quote!(<#ty as #crate_path::HasCompact>::Type)
+			} else {
+				quote!(#ty)
+			};
+			if is_generic {
+				quote_spanned! {
+					ty.span() => .saturating_add(<#counted_ty as #crate_path::MaxEncodedLen>::max_encoded_len())
+				}
+			} else {
+				quote_spanned! {
+					ty.span() => .saturating_add(
+						<#counted_ty as #crate_path::MaxEncodedLenField>::field_max_encoded_len()
+					)
+				}
+			}
+		};
+
+		match utils::get_pad(field) {
+			Some(pad) => {
+				let padding = pad - 1;
+				quote! { #length .saturating_add(#padding) }
+			},
+			None => length,
 		}
 	});
 	quote! {
@@ -105,32 +179,40 @@ fn fields_length_expr(fields: &Fields) -> proc_macro2::TokenStream {
 }
 
 // generate an expression to sum up the max encoded length of each field
-fn data_length_expr(data: &Data) -> proc_macro2::TokenStream {
+fn data_length_expr(data: &Data, crate_path: &Path, type_params: &[Ident]) -> proc_macro2::TokenStream {
 	match *data {
-		Data::Struct(ref data) => fields_length_expr(&data.fields),
+		Data::Struct(ref data) => fields_length_expr(&data.fields, crate_path, type_params),
 		Data::Enum(ref data) => {
 			// We need an expression expanded for each variant like
 			//
 			//   0
 			//     .max(<variant expression>)
 			//     .max(<variant expression>)
-			//     .saturating_add(1)
+			//     .saturating_add(<index size>)
 			//
-			// The 1 derives from the discriminant; see
-			// https://github.com/paritytech/parity-scale-codec/
-			//   blob/f0341dabb01aa9ff0548558abb6dcc5c31c669a1/derive/src/encode.rs#L211-L216
+			// The index size is the size of the variant index, which depends on the number of
+			// variants; see `utils::variant_index_type`.
 			//
 			// Each variant expression's sum is computed the way an equivalent struct's would be.
 
-			let expansion = data.variants.iter().map(|variant| {
-				let variant_expression = fields_length_expr(&variant.fields);
+			let variants = data.variants.iter().filter(|v| !utils::should_skip(&v.attrs));
+			let expansion = variants.map(|variant| {
+				// The `unknown` variant holding a raw discriminant is encoded as just that byte.
+				let variant_expression = if utils::captures_discriminant(variant) {
+					quote!(0_usize)
+				} else {
+					fields_length_expr(&variant.fields, crate_path, type_params)
+				};
 				quote! {
 					.max(#variant_expression)
 				}
 			});
 
+			let variant_count = data.variants.iter().filter(|v| !utils::should_skip(&v.attrs)).count();
+			let (_, index_size) = utils::variant_index_type(variant_count);
+
 			quote! {
-				0_usize #( #expansion )* .saturating_add(1)
+				0_usize #( #expansion )* .saturating_add(#index_size)
 			}
 		}
 		Data::Union(ref data) => {
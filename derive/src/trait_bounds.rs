@@ -38,7 +38,7 @@ impl<'a, 'ast> Visit<'ast> for ContainIdents<'a> {
 }
 
 /// Checks if the given type contains one of the given idents.
-fn type_contain_idents(ty: &Type, idents: &[Ident]) -> bool {
+pub fn type_contain_idents(ty: &Type, idents: &[Ident]) -> bool {
 	let mut visitor = ContainIdents { result: false, idents };
 	visitor.visit_type(ty);
 	visitor.result
@@ -176,6 +176,7 @@ fn get_types_to_add_trait_bound(
 	} else {
 		let needs_codec_bound = |f: &syn::Field| !utils::is_compact(f)
 				&& utils::get_encoded_as_type(f).is_none()
+				&& utils::get_with_module(f).is_none()
 				&& !utils::should_skip(&f.attrs);
 		let res = collect_types(&data, needs_codec_bound)?
 			.into_iter()
@@ -200,7 +201,7 @@ fn get_types_to_add_trait_bound(
 	}
 }
 
-fn collect_types(
+pub fn collect_types(
 	data: &syn::Data,
 	type_filter: fn(&syn::Field) -> bool,
 ) -> Result<Vec<syn::Type>> {
@@ -23,7 +23,7 @@ use proc_macro_crate::{crate_name, FoundCrate};
 use proc_macro2::{Span, Ident, TokenStream};
 use quote::quote;
 use syn::{
-	Attribute, Data, DeriveInput, Error, Field, Fields, FieldsNamed, FieldsUnnamed, Lit, Meta,
+	Attribute, Data, DataEnum, DeriveInput, Error, Field, Fields, FieldsNamed, FieldsUnnamed, Lit, Meta,
 	MetaNameValue, NestedMeta, parse::Parse, Path, punctuated::Punctuated,
 	spanned::Spanned, token, Variant,
 };
@@ -63,6 +63,20 @@ pub fn variant_index(v: &Variant, i: usize) -> TokenStream {
 		)
 }
 
+/// The integer type the variant index of an enum with `variant_count` non skipped variants is
+/// encoded as, together with its size in bytes.
+///
+/// This is `u8` for up to 256 variants, `u16` for up to 65536 variants and `u32` otherwise.
+pub fn variant_index_type(variant_count: usize) -> (TokenStream, usize) {
+	if variant_count <= 1 << 8 {
+		(quote! { ::core::primitive::u8 }, 1)
+	} else if variant_count <= 1 << 16 {
+		(quote! { ::core::primitive::u16 }, 2)
+	} else {
+		(quote! { ::core::primitive::u32 }, 4)
+	}
+}
+
 /// Look for a `#[codec(encoded_as = "SomeType")]` outer attribute on the given
 /// `Field`.
 pub fn get_encoded_as_type(field: &Field) -> Option<TokenStream> {
@@ -82,6 +96,24 @@ pub fn get_encoded_as_type(field: &Field) -> Option<TokenStream> {
 	})
 }
 
+/// Look for a `#[codec(with = "$module")]` outer attribute on the given `Field`.
+pub fn get_with_module(field: &Field) -> Option<syn::Path> {
+	find_meta_item(field.attrs.iter(), |meta| {
+		if let NestedMeta::Meta(Meta::NameValue(ref nv)) = meta {
+			if nv.path.is_ident("with") {
+				if let Lit::Str(ref s) = nv.lit {
+					return Some(
+						s.parse::<syn::Path>()
+							.expect("Internal error, with attribute must have been checked")
+					);
+				}
+			}
+		}
+
+		None
+	})
+}
+
 /// Look for a `#[codec(compact)]` outer attribute on the given `Field`.
 pub fn is_compact(field: &Field) -> bool {
 	find_meta_item(field.attrs.iter(), |meta| {
@@ -95,6 +127,23 @@ pub fn is_compact(field: &Field) -> bool {
 	}).is_some()
 }
 
+/// Look for a `#[codec(pad = $int)]` outer attribute on the given `Field`.
+pub fn get_pad(field: &Field) -> Option<usize> {
+	find_meta_item(field.attrs.iter(), |meta| {
+		if let NestedMeta::Meta(Meta::NameValue(ref nv)) = meta {
+			if nv.path.is_ident("pad") {
+				if let Lit::Int(ref v) = nv.lit {
+					let pad = v.base10_parse::<usize>()
+						.expect("Internal error, pad attribute must have been checked");
+					return Some(pad)
+				}
+			}
+		}
+
+		None
+	})
+}
+
 /// Look for a `#[codec(skip)]` in the given attributes.
 pub fn should_skip(attrs: &[Attribute]) -> bool {
 	find_meta_item(attrs.iter(), |meta| {
@@ -108,6 +157,107 @@ pub fn should_skip(attrs: &[Attribute]) -> bool {
 	}).is_some()
 }
 
+/// Look for a `#[codec(unknown)]` in the given attributes.
+pub fn is_unknown_variant(attrs: &[Attribute]) -> bool {
+	find_meta_item(attrs.iter(), |meta| {
+		if let NestedMeta::Meta(Meta::Path(ref path)) = meta {
+			if path.is_ident("unknown") {
+				return Some(());
+			}
+		}
+
+		None
+	}).is_some()
+}
+
+/// Look for a `#[codec(unknown_with = "$expr")]` in the given attributes.
+pub fn get_unknown_with(attrs: &[Attribute]) -> Option<syn::Expr> {
+	find_meta_item(attrs.iter(), |meta| {
+		if let NestedMeta::Meta(Meta::NameValue(ref nv)) = meta {
+			if nv.path.is_ident("unknown_with") {
+				if let Lit::Str(ref s) = nv.lit {
+					return Some(
+						s.parse::<syn::Expr>()
+							.expect("Internal error, unknown_with attribute must have been checked")
+					);
+				}
+			}
+		}
+
+		None
+	})
+}
+
+/// Whether the given type is a `PhantomData`, possibly through a path like
+/// `core::marker::PhantomData<T>`.
+#[cfg(feature = "max-encoded-len")]
+pub fn is_phantom_data(ty: &syn::Type) -> bool {
+	match ty {
+		syn::Type::Path(ref type_path) => matches!(
+			type_path.path.segments.last(),
+			Some(segment) if segment.ident == "PhantomData"
+		),
+		_ => false,
+	}
+}
+
+/// Whether the given variant is marked `#[codec(unknown)]` and stores the raw discriminant in
+/// its field.
+pub fn captures_discriminant(variant: &Variant) -> bool {
+	is_unknown_variant(&variant.attrs) && matches!(variant.fields, Fields::Unnamed(_))
+}
+
+/// Ensure at most one variant of the enum is marked `#[codec(unknown)]` or
+/// `#[codec(unknown_with = "$expr")]`, and that this variant is not skipped. An `unknown` variant
+/// must be either a unit variant or have a single unnamed field.
+pub fn check_unknown_variant(data: &DataEnum) -> syn::Result<()> {
+	let mut unknown = data.variants.iter()
+		.filter(|v| is_unknown_variant(&v.attrs) || get_unknown_with(&v.attrs).is_some());
+
+	if let Some(variant) = unknown.next() {
+		if !is_unknown_variant(&variant.attrs) {
+			if should_skip(&variant.attrs) {
+				return Err(Error::new(variant.span(), "`unknown_with` and `skip` can not be used together"));
+			}
+			return match unknown.next() {
+				Some(variant) => Err(Error::new(
+					variant.span(),
+					"Only one variant can be marked `unknown` or `unknown_with`",
+				)),
+				None => Ok(()),
+			};
+		}
+
+		if get_unknown_with(&variant.attrs).is_some() {
+			return Err(Error::new(variant.span(), "`unknown` and `unknown_with` can not be used together"));
+		}
+
+		if should_skip(&variant.attrs) {
+			return Err(Error::new(variant.span(), "`unknown` and `skip` can not be used together"));
+		}
+
+		let variant_count = data.variants.iter().filter(|v| !should_skip(&v.attrs)).count();
+		match variant.fields {
+			Fields::Unit => (),
+			Fields::Unnamed(_) if variant_count > 1 << 8 => return Err(Error::new(
+				variant.span(),
+				"The `unknown` variant can only store the raw index of enums with at most 256 variants",
+			)),
+			Fields::Unnamed(ref fields) if fields.unnamed.len() == 1 => (),
+			_ => return Err(Error::new(
+				variant.span(),
+				"The `unknown` variant must be a unit variant or have a single unnamed `u8` field",
+			)),
+		}
+	}
+
+	if let Some(variant) = unknown.next() {
+		return Err(Error::new(variant.span(), "Only one variant can be marked `unknown` or `unknown_with`"));
+	}
+
+	Ok(())
+}
+
 /// Look for a `#[codec(dumb_trait_bound)]`in the given attributes.
 pub fn has_dumb_trait_bound(attrs: &[Attribute]) -> bool {
 	find_meta_item(attrs.iter(), |meta| {
@@ -121,6 +271,66 @@ pub fn has_dumb_trait_bound(attrs: &[Attribute]) -> bool {
 	}).is_some()
 }
 
+/// Look for a `#[codec(transparent)]` in the given attributes.
+pub fn is_transparent(attrs: &[Attribute]) -> bool {
+	find_meta_item(attrs.iter(), |meta| {
+		if let NestedMeta::Meta(Meta::Path(ref path)) = meta {
+			if path.is_ident("transparent") {
+				return Some(());
+			}
+		}
+
+		None
+	}).is_some()
+}
+
+/// Look for a `#[codec(variant_names)]` in the given attributes.
+pub fn has_variant_names(attrs: &[Attribute]) -> bool {
+	find_meta_item(attrs.iter(), |meta| {
+		if let NestedMeta::Meta(Meta::Path(ref path)) = meta {
+			if path.is_ident("variant_names") {
+				return Some(());
+			}
+		}
+
+		None
+	}).is_some()
+}
+
+/// Get the type of the field a `#[codec(transparent)]` struct is encoded as.
+///
+/// Returns `None` if the struct isn't transparent.
+pub fn transparent_field_type<'a>(attrs: &[Attribute], data: &'a Data) -> Option<&'a syn::Type> {
+	if !is_transparent(attrs) {
+		return None;
+	}
+
+	match *data {
+		Data::Struct(ref data) => data.fields.iter().find(|f| !should_skip(&f.attrs)).map(|f| &f.ty),
+		_ => None,
+	}
+}
+
+/// Look for a `#[codec(validate = "path::to::fn")]` in the given attributes.
+///
+/// If found, the function is called with the decoded value before `Decode::decode` returns.
+pub fn get_validate_fn(attrs: &[Attribute]) -> Option<Path> {
+	find_meta_item(attrs.iter(), |meta| {
+		if let NestedMeta::Meta(Meta::NameValue(ref nv)) = meta {
+			if nv.path.is_ident("validate") {
+				if let Lit::Str(ref s) = nv.lit {
+					return Some(
+						s.parse::<Path>()
+							.expect("Internal error, validate attribute must have been checked")
+					);
+				}
+			}
+		}
+
+		None
+	})
+}
+
 /// Generate the crate access for the crate using 2018 syntax.
 fn crate_access() -> syn::Result<Ident> {
 	const DEF_CRATE: &str = "parity-scale-codec";
@@ -232,14 +442,14 @@ pub fn custom_mel_trait_bound(attrs: &[Attribute]) -> Option<TraitBounds> {
 
 /// Given a set of named fields, return an iterator of `Field` where all fields
 /// marked `#[codec(skip)]` are filtered out.
-pub fn filter_skip_named<'a>(fields: &'a syn::FieldsNamed) -> impl Iterator<Item=&Field> + 'a {
+pub fn filter_skip_named<'a>(fields: &'a syn::FieldsNamed) -> impl Iterator<Item=&'a Field> + 'a {
 	fields.named.iter()
 		.filter(|f| !should_skip(&f.attrs))
 }
 
 /// Given a set of unnamed fields, return an iterator of `(index, Field)` where all fields
 /// marked `#[codec(skip)]` are filtered out.
-pub fn filter_skip_unnamed<'a>(fields: &'a syn::FieldsUnnamed) -> impl Iterator<Item=(usize, &Field)> + 'a {
+pub fn filter_skip_unnamed<'a>(fields: &'a syn::FieldsUnnamed) -> impl Iterator<Item=(usize, &'a Field)> + 'a {
 	fields.unnamed.iter()
 		.enumerate()
 		.filter(|(_, f)| !should_skip(&f.attrs))
@@ -256,6 +466,8 @@ pub fn filter_skip_unnamed<'a>(fields: &'a syn::FieldsUnnamed) -> impl Iterator<
 /// * `#[codec(decode_bound(T: Decode))]`
 /// * `#[codec(mel_bound(T: MaxEncodedLen))]`
 /// * `#[codec(crate = path::to::crate)]
+/// * `#[codec(validate = "path::to::fn")]`
+/// * `#[codec(variant_names)]`
 ///
 /// Fields can have the following attributes:
 ///
@@ -267,11 +479,20 @@ pub fn filter_skip_unnamed<'a>(fields: &'a syn::FieldsUnnamed) -> impl Iterator<
 ///
 /// * `#[codec(skip)]`
 /// * `#[codec(index = $int)]`
+/// * `#[codec(unknown)]`
 pub fn check_attributes(input: &DeriveInput) -> syn::Result<()> {
 	for attr in &input.attrs {
 		check_top_attribute(attr)?;
 	}
 
+	if is_transparent(&input.attrs) {
+		check_transparent(input)?;
+	}
+
+	if has_variant_names(&input.attrs) {
+		check_variant_names(input)?;
+	}
+
 	match input.data {
 		Data::Struct(ref data) => match &data.fields {
 			| Fields::Named(FieldsNamed { named: fields , .. })
@@ -285,6 +506,8 @@ pub fn check_attributes(input: &DeriveInput) -> syn::Result<()> {
 			Fields::Unit => (),
 		}
 		Data::Enum(ref data) => {
+			check_unknown_variant(data)?;
+
 			for variant in data.variants.iter() {
 				for attr in &variant.attrs {
 					check_variant_attribute(attr)?;
@@ -301,6 +524,52 @@ pub fn check_attributes(input: &DeriveInput) -> syn::Result<()> {
 	Ok(())
 }
 
+// Ensure a `#[codec(transparent)]` type is a struct with exactly one non skipped field, encoded
+// as is.
+fn check_transparent(input: &DeriveInput) -> syn::Result<()> {
+	let transparent_error = "`#[codec(transparent)]` is only accepted on structs with exactly one \
+		non skipped field";
+	let fields = match input.data {
+		Data::Struct(ref data) => &data.fields,
+		_ => return Err(syn::Error::new(input.ident.span(), transparent_error)),
+	};
+
+	let mut encoded = fields.iter().filter(|f| !should_skip(&f.attrs));
+	match (encoded.next(), encoded.next()) {
+		(Some(field), None) => {
+			if is_compact(field)
+				|| get_encoded_as_type(field).is_some()
+				|| get_with_module(field).is_some()
+				|| get_pad(field).is_some()
+			{
+				Err(syn::Error::new(
+					field.span(),
+					"The field of a `#[codec(transparent)]` struct can't be `compact`, \
+						`encoded_as`, `with` or `pad`",
+				))
+			} else {
+				Ok(())
+			}
+		},
+		_ => Err(syn::Error::new(input.ident.span(), transparent_error)),
+	}
+}
+
+// Ensure a `#[codec(variant_names)]` type is an enum without skipped variants, each variant
+// having an index.
+fn check_variant_names(input: &DeriveInput) -> syn::Result<()> {
+	match input.data {
+		Data::Enum(ref data) => match data.variants.iter().find(|v| should_skip(&v.attrs)) {
+			Some(variant) => Err(syn::Error::new(
+				variant.span(),
+				"`#[codec(variant_names)]` can't be used with skipped variants, they have no index",
+			)),
+			None => Ok(()),
+		},
+		_ => Err(syn::Error::new(input.ident.span(), "`#[codec(variant_names)]` is only accepted on enums")),
+	}
+}
+
 // Check if the attribute is `#[allow(..)]`, `#[deny(..)]`, `#[forbid(..)]` or `#[warn(..)]`.
 pub fn is_lint_attribute(attr: &Attribute) -> bool {
 	attr.path.is_ident("allow")
@@ -313,9 +582,12 @@ pub fn is_lint_attribute(attr: &Attribute) -> bool {
 // * `#[codec(skip)]`
 // * `#[codec(compact)]`
 // * `#[codec(encoded_as = "$EncodeAs")]` with $EncodedAs a valid TokenStream
+// * `#[codec(with = "$module")]` with $module a valid path
+// * `#[codec(pad = $int)]`
 fn check_field_attribute(attr: &Attribute) -> syn::Result<()> {
-	let field_error = "Invalid attribute on field, only `#[codec(skip)]`, `#[codec(compact)]` and \
-		`#[codec(encoded_as = \"$EncodeAs\")]` are accepted.";
+	let field_error = "Invalid attribute on field, only `#[codec(skip)]`, `#[codec(compact)]`, \
+		`#[codec(encoded_as = \"$EncodeAs\")]`, `#[codec(with = \"$module\")]` and `#[codec(pad = $int)]` \
+		are accepted.";
 
 	if attr.path.is_ident("codec") {
 		match attr.parse_meta()? {
@@ -332,6 +604,18 @@ fn check_field_attribute(attr: &Attribute) -> syn::Result<()> {
 					=> TokenStream::from_str(&lit_str.value()).map(|_| ())
 						.map_err(|_e| syn::Error::new(lit_str.span(), "Invalid token stream")),
 
+					NestedMeta::Meta(Meta::NameValue(MetaNameValue { path, lit: Lit::Str(lit_str), .. }))
+						if path.is_ident("with")
+					=> lit_str.parse::<syn::Path>().map(|_| ())
+						.map_err(|_e| syn::Error::new(lit_str.span(), "Invalid module path")),
+
+					NestedMeta::Meta(Meta::NameValue(MetaNameValue { path, lit: Lit::Int(lit_int), .. }))
+						if path.is_ident("pad")
+					=> match lit_int.base10_parse::<usize>() {
+						Ok(pad) if pad > 0 => Ok(()),
+						_ => Err(syn::Error::new(lit_int.span(), "Padding must be a positive integer")),
+					},
+
 					elt @ _ => Err(syn::Error::new(elt.span(), field_error)),
 				}
 			},
@@ -345,9 +629,11 @@ fn check_field_attribute(attr: &Attribute) -> syn::Result<()> {
 // Ensure a field is decorated only with the following attributes:
 // * `#[codec(skip)]`
 // * `#[codec(index = $int)]`
+// * `#[codec(unknown)]`
+// * `#[codec(unknown_with = "$expr")]` with $expr a valid expression
 fn check_variant_attribute(attr: &Attribute) -> syn::Result<()> {
-	let variant_error = "Invalid attribute on variant, only `#[codec(skip)]` and \
-		`#[codec(index = $u8)]` are accepted.";
+	let variant_error = "Invalid attribute on variant, only `#[codec(skip)]`, \
+		`#[codec(index = $u8)]`, `#[codec(unknown)]` and `#[codec(unknown_with = \"$expr\")]` are accepted.";
 
 	if attr.path.is_ident("codec") {
 		match attr.parse_meta()? {
@@ -356,6 +642,14 @@ fn check_variant_attribute(attr: &Attribute) -> syn::Result<()> {
 					NestedMeta::Meta(Meta::Path(path))
 						if path.get_ident().map_or(false, |i| i == "skip") => Ok(()),
 
+					NestedMeta::Meta(Meta::Path(path))
+						if path.is_ident("unknown") => Ok(()),
+
+					NestedMeta::Meta(Meta::NameValue(MetaNameValue { path, lit: Lit::Str(lit_str), .. }))
+						if path.is_ident("unknown_with")
+					=> lit_str.parse::<syn::Expr>().map(|_| ())
+						.map_err(|_| syn::Error::new(lit_str.span(), "Invalid expression")),
+
 					NestedMeta::Meta(Meta::NameValue(MetaNameValue { path, lit: Lit::Int(lit_int), .. }))
 						if path.get_ident().map_or(false, |i| i == "index")
 					=> lit_int.base10_parse::<u8>().map(|_| ())
@@ -374,9 +668,9 @@ fn check_variant_attribute(attr: &Attribute) -> syn::Result<()> {
 // Only `#[codec(dumb_trait_bound)]` is accepted as top attribute
 fn check_top_attribute(attr: &Attribute) -> syn::Result<()> {
 	let top_error = "Invalid attribute: only `#[codec(dumb_trait_bound)]`, \
-		`#[codec(crate = path::to::crate)]`, `#[codec(encode_bound(T: Encode))]`, \
-		`#[codec(decode_bound(T: Decode))]`, or `#[codec(mel_bound(T: MaxEncodedLen))]` \
-		are accepted as top attribute";
+		`#[codec(transparent)]`, `#[codec(crate = path::to::crate)]`, `#[codec(encode_bound(T: Encode))]`, \
+		`#[codec(decode_bound(T: Decode))]`, `#[codec(mel_bound(T: MaxEncodedLen))]`, \
+		`#[codec(validate = \"path::to::fn\")]` or `#[codec(variant_names)]` are accepted as top attribute";
 	if attr.path.is_ident("codec")
 		&& attr.parse_args::<CustomTraitBound<encode_bound>>().is_err()
 		&& attr.parse_args::<CustomTraitBound<decode_bound>>().is_err()
@@ -389,6 +683,17 @@ fn check_top_attribute(attr: &Attribute) -> syn::Result<()> {
 						NestedMeta::Meta(Meta::Path(path))
 							if path.get_ident().map_or(false, |i| i == "dumb_trait_bound") => Ok(()),
 
+						NestedMeta::Meta(Meta::Path(path))
+							if path.is_ident("transparent") => Ok(()),
+
+						NestedMeta::Meta(Meta::Path(path))
+							if path.is_ident("variant_names") => Ok(()),
+
+						NestedMeta::Meta(Meta::NameValue(MetaNameValue { path, lit: Lit::Str(lit_str), .. }))
+							if path.is_ident("validate")
+						=> lit_str.parse::<Path>().map(|_| ())
+							.map_err(|_e| syn::Error::new(lit_str.span(), "Invalid path")),
+
 						elt @ _ => Err(syn::Error::new(elt.span(), top_error)),
 					}
 			}
@@ -13,12 +13,12 @@ pub struct MockStruct{
 
 /// Used for implementing the Arbitrary trait for a BitVec.
 #[derive(Encode, Decode, Clone, PartialEq, Debug)]
-pub struct BitVecWrapper<O: BitOrder, T: BitStore>(BitVec<O, T>);
+pub struct BitVecWrapper<O: BitOrder, T: BitStore>(BitVec<T, O>);
 
 impl<'a, O: 'static + BitOrder, T: 'static + BitStore + Arbitrary<'a>> Arbitrary<'a> for BitVecWrapper<O, T> {
 	fn arbitrary(u: &mut Unstructured<'a>) -> ArbResult<Self> {
 		let v = Vec::<T>::arbitrary(u)?;
-		Ok(BitVecWrapper(BitVec::<O, T>::from_vec(v)))
+		Ok(BitVecWrapper(BitVec::<T, O>::from_vec(v)))
 	}
 }
 
@@ -226,8 +226,8 @@ fn fuzz_decode(data: &[u8]) {
 		VecDeque<u8>,
 		MockStruct,
 		MockEnum,
-		BitVec<Msb0, u8>,
-		BitVec<Msb0, u32>,
+		BitVec<u8, Msb0>,
+		BitVec<u32, Msb0>,
 		Duration,
 	};
 	// Types for which we wish to apply the "sorted" method.
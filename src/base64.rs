@@ -0,0 +1,125 @@
+// Copyright 2021 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Base64 conversion of encoded values, for `Encode::encode_base64` and `Decode::decode_base64`.
+//!
+//! The standard alphabet of RFC 4648 (`A-Z`, `a-z`, `0-9`, `+` and `/`) is used, with `=`
+//! padding. Decoding is strict: the padding is required, no whitespace is allowed and the unused
+//! bits of the last digit must be zero, so each byte string has a single base64 representation.
+
+use crate::alloc::{string::String, vec::Vec};
+use crate::Error;
+
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const PAD: u8 = b'=';
+
+/// Write `bytes` as base64.
+pub(crate) fn encode(bytes: &[u8]) -> String {
+	let mut result = String::with_capacity(bytes.chunks(3).len() * 4);
+	for chunk in bytes.chunks(3) {
+		let group = chunk.iter().enumerate().fold(0u32, |group, (i, b)| group | (*b as u32) << (16 - 8 * i));
+		for i in 0..4 {
+			if i <= chunk.len() {
+				result.push(ALPHABET[(group >> (18 - 6 * i) & 0x3f) as usize] as char);
+			} else {
+				result.push(PAD as char);
+			}
+		}
+	}
+	result
+}
+
+/// Parse the bytes written as base64 in `base64`.
+pub(crate) fn decode(base64: &str) -> Result<Vec<u8>, Error> {
+	let chunks = base64.as_bytes().chunks_exact(4);
+	if !chunks.remainder().is_empty() {
+		return Err("Base64 string length is not a multiple of 4".into());
+	}
+
+	let groups = chunks.len();
+	let mut result = Vec::with_capacity(groups * 3);
+	for (n, chunk) in chunks.enumerate() {
+		let padding = if n + 1 == groups {
+			chunk.iter().rev().take_while(|c| **c == PAD).count().min(2)
+		} else {
+			0
+		};
+		let digits = 4 - padding;
+
+		let mut group = 0u32;
+		for (i, c) in chunk[..digits].iter().enumerate() {
+			group |= (digit(*c)? as u32) << (18 - 6 * i);
+		}
+		let len = digits - 1;
+		if group & (0xff_ffff >> (8 * len)) != 0 {
+			return Err("Base64 string has non zero trailing bits".into());
+		}
+		result.extend(group.to_be_bytes()[1..=len].iter());
+	}
+	Ok(result)
+}
+
+fn digit(c: u8) -> Result<u8, Error> {
+	match c {
+		b'A'..=b'Z' => Ok(c - b'A'),
+		b'a'..=b'z' => Ok(c - b'a' + 26),
+		b'0'..=b'9' => Ok(c - b'0' + 52),
+		b'+' => Ok(62),
+		b'/' => Ok(63),
+		_ => Err("Invalid base64 digit".into()),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{Decode, Encode};
+
+	#[test]
+	fn base64_rfc_4648_vectors() {
+		let vectors = [
+			("", ""), ("f", "Zg=="), ("fo", "Zm8="), ("foo", "Zm9v"), ("foob", "Zm9vYg=="),
+			("fooba", "Zm9vYmE="), ("foobar", "Zm9vYmFy"),
+		];
+		for (bytes, base64) in vectors.iter() {
+			assert_eq!(encode(bytes.as_bytes()), *base64);
+			assert_eq!(decode(base64), Ok(bytes.as_bytes().to_vec()));
+		}
+		assert_eq!(encode(&[0xfb, 0xff]), "+/8=");
+	}
+
+	#[test]
+	fn base64_rejects_invalid_strings() {
+		assert!(decode("Zg=").is_err());
+		assert!(decode("Zg").is_err());
+		assert!(decode("Zh==").is_err());
+		assert!(decode("Zm9=").is_err());
+		assert!(decode("Z===").is_err());
+		assert!(decode("Zg==Zg==").is_err());
+		assert!(decode("Zm9v\n").is_err());
+		assert!(decode("Zm-_").is_err());
+	}
+
+	#[test]
+	fn encode_and_decode_base64_roundtrip() {
+		let value = (42u32, vec![Some(true), None], String::from("scale"));
+		let base64 = value.encode_base64();
+		assert_eq!(base64, encode(&value.encode()));
+		assert_eq!(<(u32, Vec<Option<bool>>, String)>::decode_base64(&base64), Ok(value));
+
+		assert_eq!(u32::decode_base64("AQAAAA=="), Ok(1));
+		assert!(u32::decode_base64("AQAA").is_err());
+		assert!(u16::decode_base64("AQAAAA==").is_err());
+	}
+}
@@ -0,0 +1,156 @@
+// Copyright 2021 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! `num-bigint` specific serialization.
+//!
+//! `BigUint` is encoded as its little-endian bytes prefixed with their compact encoded length,
+//! i.e. like a `Vec<u8>`. The encoding is canonical: the most significant byte is never zero, so
+//! zero is encoded as an empty byte sequence. Decoding rejects any other form.
+//!
+//! `BigInt` is encoded as a sign byte (`0` for zero, `1` for positive, `2` for negative) followed by
+//! the encoding of its magnitude as a `BigUint`. Zero must use sign `0`, and a non-zero value must
+//! not.
+
+use core::mem;
+
+use num_bigint::{BigInt, BigUint, Sign};
+
+use crate::alloc::vec::Vec;
+use crate::{Encode, Decode, Input, Output, Error};
+use crate::encode_like::EncodeLike;
+
+/// Returns the canonical little-endian bytes of `value`.
+fn canonical_bytes(value: &BigUint) -> Vec<u8> {
+	if value.bits() == 0 {
+		Vec::new()
+	} else {
+		value.to_bytes_le()
+	}
+}
+
+impl Encode for BigUint {
+	fn size_hint(&self) -> usize {
+		mem::size_of::<u32>() + (self.bits() / 8 + 1) as usize
+	}
+
+	fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+		canonical_bytes(self).encode_to(dest)
+	}
+}
+
+impl EncodeLike for BigUint {}
+
+impl Decode for BigUint {
+	fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+		let bytes = <Vec<u8>>::decode(input)?;
+		if bytes.last() == Some(&0) {
+			return Err("Non-canonical `BigUint`: most significant byte is zero".into());
+		}
+
+		Ok(BigUint::from_bytes_le(&bytes))
+	}
+}
+
+impl Encode for BigInt {
+	fn size_hint(&self) -> usize {
+		1 + self.magnitude().size_hint()
+	}
+
+	fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+		let sign: u8 = match self.sign() {
+			Sign::NoSign => 0,
+			Sign::Plus => 1,
+			Sign::Minus => 2,
+		};
+		dest.push_byte(sign);
+		self.magnitude().encode_to(dest)
+	}
+}
+
+impl EncodeLike for BigInt {}
+
+impl Decode for BigInt {
+	fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+		let sign = input.read_byte()
+			.map_err(|e| e.chain("Could not decode `BigInt`, failed to read sign byte"))?;
+		let magnitude = BigUint::decode(input)?;
+
+		let sign = match (sign, magnitude.bits() == 0) {
+			(0, true) => Sign::NoSign,
+			(1, false) => Sign::Plus,
+			(2, false) => Sign::Minus,
+			_ => return Err("Invalid `BigInt` sign byte".into()),
+		};
+
+		Ok(BigInt::from_biguint(sign, magnitude))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn forty_bytes() -> BigUint {
+		BigUint::from_bytes_le(&(1..=40).collect::<Vec<u8>>())
+	}
+
+	#[test]
+	fn biguint_roundtrip() {
+		for value in &[BigUint::from(0u8), BigUint::from(1u8), BigUint::from(u128::max_value()), forty_bytes()] {
+			let encoded = value.encode();
+			assert!(encoded.len() <= value.size_hint());
+			assert_eq!(&BigUint::decode(&mut &encoded[..]).unwrap(), value);
+		}
+	}
+
+	#[test]
+	fn biguint_encoding_is_canonical() {
+		assert_eq!(BigUint::from(0u8).encode(), vec![0]);
+		assert_eq!(BigUint::from(0x1234u16).encode(), vec![2 << 2, 0x34, 0x12]);
+
+		let mut expected = vec![40 << 2];
+		expected.extend(1..=40);
+		assert_eq!(forty_bytes().encode(), expected);
+
+		assert!(BigUint::decode(&mut &[1 << 2, 0][..]).is_err());
+		assert!(BigUint::decode(&mut &[2 << 2, 0x34, 0][..]).is_err());
+	}
+
+	#[test]
+	fn bigint_roundtrip() {
+		let values = [
+			BigInt::from(0),
+			BigInt::from(1),
+			BigInt::from(-1),
+			BigInt::from(i128::min_value()),
+			BigInt::from_biguint(Sign::Minus, forty_bytes()),
+		];
+		for value in &values {
+			let encoded = value.encode();
+			assert!(encoded.len() <= value.size_hint());
+			assert_eq!(&BigInt::decode(&mut &encoded[..]).unwrap(), value);
+		}
+
+		assert_eq!(BigInt::from(0).encode(), vec![0, 0]);
+		assert_eq!(BigInt::from(-5).encode(), vec![2, 1 << 2, 5]);
+	}
+
+	#[test]
+	fn bigint_rejects_invalid_sign() {
+		assert!(BigInt::decode(&mut &[0, 1 << 2, 5][..]).is_err());
+		assert!(BigInt::decode(&mut &[1, 0][..]).is_err());
+		assert!(BigInt::decode(&mut &[2, 0][..]).is_err());
+		assert!(BigInt::decode(&mut &[3, 1 << 2, 5][..]).is_err());
+	}
+}
@@ -15,44 +15,101 @@
 //! `BitVec` specific serialization.
 
 use bitvec::{
-	vec::BitVec, store::BitStore, order::BitOrder, slice::BitSlice, boxed::BitBox, mem::BitMemory
+	vec::BitVec, store::BitStore, order::BitOrder, slice::BitSlice, boxed::BitBox, array::BitArray,
+	view::{BitView, BitViewSized}, mem::bits_of,
 };
 use crate::{
 	EncodeLike, Encode, Decode, Input, Output, Error, Compact,
+	alloc::vec::Vec,
 	codec::{decode_vec_with_len, encode_slice_no_len},
 };
 
-impl<O: BitOrder, T: BitStore + Encode> Encode for BitSlice<O, T> {
-	fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
-		let len = self.len();
-		assert!(
-			len <= u32::max_value() as usize,
-			"Attempted to serialize a collection with too many elements.",
-		);
-		Compact(len as u32).encode_to(dest);
+/// Check that the number of bits of a bitvec fits its `u32` length prefix.
+fn check_bits_len(len: usize) -> Result<u32, Error> {
+	if len > u32::max_value() as usize {
+		return Err("Attempted to serialize a collection with too many elements.".into());
+	}
+	Ok(len as u32)
+}
 
-		// NOTE: doc of `BitSlice::as_slice`:
-		// > The returned slice handle views all elements touched by self
-		//
-		// Thus we are sure the slice doesn't contain unused elements at the end.
-		let slice = self.as_slice();
+/// Encode the number of bits of a bitvec.
+fn encode_bits_len<W: Output + ?Sized>(len: usize, dest: &mut W) {
+	let len = check_bits_len(len).expect("Attempted to serialize a collection with too many elements.");
+	Compact(len).encode_to(dest);
+}
 
-		encode_slice_no_len(slice, dest)
+/// Encode `bits` like [`Encode::encode`], returning an error instead of panicking if it has more
+/// than `u32::max_value()` bits.
+///
+/// This works for a `BitVec` or a `BitBox` as well, through their `Deref` to `BitSlice`.
+///
+/// ```
+/// # use parity_scale_codec::{Encode, try_encode_bitslice};
+/// # use bitvec::{bitvec, order::Lsb0};
+/// let bits = bitvec![u8, Lsb0; 1, 0, 1];
+/// assert_eq!(try_encode_bitslice(&bits), Ok(bits.encode()));
+/// ```
+pub fn try_encode_bitslice<T: BitStore, O: BitOrder>(bits: &BitSlice<T, O>) -> Result<Vec<u8>, Error>
+where
+	BitSlice<T, O>: Encode,
+{
+	check_bits_len(bits.len())?;
+	Ok(bits.encode())
+}
+
+/// Encode the bits of `bits` as elements of `T`, each element rebuilt with only these bits.
+///
+/// This works for any slice, including one starting in the middle of an element or ending
+/// before the end of its last element.
+fn encode_rebuilt_elements<T: BitStore + Encode, O: BitOrder, W: Output + ?Sized>(
+	bits: &BitSlice<T, O>,
+	dest: &mut W,
+) {
+	for chunk in bits.chunks(bits_of::<T::Mem>()) {
+		let mut element = T::ZERO;
+		element.view_bits_mut::<O>()[..chunk.len()].copy_from_bitslice(chunk);
+		element.encode_to(dest);
 	}
 }
 
-impl<O: BitOrder, T: BitStore + Encode> Encode for BitVec<O, T> {
+/// Encode the bits of a `BitVec` or `BitBox` whose first bit is the first bit of `raw`.
+///
+/// The full elements are written from `raw` directly, only the last partial element is rebuilt.
+fn encode_from_raw<T: BitStore + Encode, O: BitOrder, W: Output + ?Sized>(
+	raw: &[T],
+	bits: &BitSlice<T, O>,
+	dest: &mut W,
+) {
+	encode_bits_len(bits.len(), dest);
+
+	let full = bits.len() / bits_of::<T::Mem>();
+	encode_slice_no_len(&raw[..full], dest);
+	encode_rebuilt_elements(&bits[full * bits_of::<T::Mem>()..], dest);
+}
+
+impl<T: BitStore + Encode, O: BitOrder> Encode for BitSlice<T, O> {
 	fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
-		self.as_bitslice().encode_to(dest)
+		encode_bits_len(self.len(), dest);
+		encode_rebuilt_elements(self, dest);
+	}
+}
+
+impl<T: BitStore + Encode, O: BitOrder> Encode for BitVec<T, O> {
+	fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+		if self.as_bitptr().bit().into_inner() == 0 {
+			encode_from_raw(self.as_raw_slice(), self, dest)
+		} else {
+			self.as_bitslice().encode_to(dest)
+		}
 	}
 }
 
-impl<O: BitOrder, T: BitStore + Encode> EncodeLike for BitVec<O, T> {}
+impl<T: BitStore + Encode, O: BitOrder> EncodeLike for BitVec<T, O> {}
 
 /// Equivalent of `BitStore::MAX_BITS` on 32bit machine.
 const ARCH32BIT_BITSLICE_MAX_BITS: usize = 0x1fff_ffff;
 
-impl<O: BitOrder, T: BitStore + Decode> Decode for BitVec<O, T> {
+impl<T: BitStore + Decode, O: BitOrder> Decode for BitVec<T, O> {
 	fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
 		<Compact<u32>>::decode(input).and_then(move |Compact(bits)| {
 			// Otherwise it is impossible to store it on 32bit machine.
@@ -69,35 +126,161 @@ impl<O: BitOrder, T: BitStore + Decode> Decode for BitVec<O, T> {
 					segment needed for `bits` to be represented; qed")
 				})?;
 
-			assert!(bits as usize <= result.len());
+			if bits as usize > result.len() {
+				return Err("Not enough elements to hold the bits of a bitvec".into());
+			}
 			result.truncate(bits as usize);
 			Ok(result)
 		})
 	}
 }
 
-impl<O: BitOrder, T: BitStore + Encode> Encode for BitBox<O, T> {
+impl<T: BitStore + Encode, O: BitOrder> Encode for BitBox<T, O> {
+	fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+		if self.as_bitptr().bit().into_inner() == 0 {
+			encode_from_raw(self.as_raw_slice(), self, dest)
+		} else {
+			self.as_bitslice().encode_to(dest)
+		}
+	}
+}
+
+impl<T: BitStore + Encode, O: BitOrder> EncodeLike for BitBox<T, O> {}
+
+impl<T: BitStore + Decode, O: BitOrder> Decode for BitBox<T, O> {
+	fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+		Ok(BitVec::<T, O>::decode(input)?.into_boxed_bitslice())
+	}
+}
+
+// A `BitArray` is encoded like a `BitVec` of the same bits. As the bits fill all the elements, the
+// elements are encoded as they are.
+impl<A: BitViewSized + Encode, O: BitOrder> Encode for BitArray<A, O> {
+	fn size_hint(&self) -> usize {
+		crate::compact_len(bits_of::<A>() as u64) + self.data.size_hint()
+	}
+
+	fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+		Compact(bits_of::<A>() as u32).encode_to(dest);
+		self.data.encode_to(dest)
+	}
+}
+
+impl<A: BitViewSized + Encode, O: BitOrder> EncodeLike for BitArray<A, O> {}
+
+impl<A: BitViewSized + Decode, O: BitOrder> Decode for BitArray<A, O> {
+	fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+		let bits = <Compact<u32>>::decode(input)?.0;
+		if bits as usize != bits_of::<A>() {
+			return Err("Attempt to decode a BitArray with the wrong number of bits".into());
+		}
+
+		Ok(Self::new(A::decode(input)?))
+	}
+}
+
+#[cfg(feature = "max-encoded-len")]
+impl<A: BitViewSized + crate::MaxEncodedLen, O: BitOrder> crate::MaxEncodedLen for BitArray<A, O> {
+	fn max_encoded_len() -> usize {
+		crate::compact_len(bits_of::<A>() as u64).saturating_add(A::max_encoded_len())
+	}
+}
+
+/// Decode a `BitSlice` borrowing its bits from `input`, instead of allocating a `BitVec`.
+///
+/// This reads the encoding of a `BitVec<u8, O>`. Only `u8` elements can be borrowed, as larger
+/// elements are encoded little endian and the input may not be aligned for them.
+///
+/// ```
+/// # use parity_scale_codec::{Encode, decode_bitslice};
+/// # use bitvec::{bitvec, order::Msb0};
+/// let encoded = bitvec![u8, Msb0; 1, 0, 1, 1].encode();
+/// let mut input = &encoded[..];
+/// let bits = decode_bitslice::<Msb0>(&mut input).unwrap();
+/// assert_eq!(bits, bitvec![u8, Msb0; 1, 0, 1, 1]);
+/// assert!(input.is_empty());
+/// ```
+pub fn decode_bitslice<'a, O: BitOrder>(input: &mut &'a [u8]) -> Result<&'a BitSlice<u8, O>, Error> {
+	let bits = <Compact<u32>>::decode(input)?.0;
+	if bits as usize > ARCH32BIT_BITSLICE_MAX_BITS {
+		return Err("Attempt to decode a bitvec with too many bits".into());
+	}
+
+	let len = required_elements::<u8>(bits)? as usize;
+	if len > input.len() {
+		return Err("Not enough data to fill buffer".into());
+	}
+	let (data, rest) = input.split_at(len);
+	*input = rest;
+
+	Ok(&BitSlice::from_slice(data)[..bits as usize])
+}
+
+/// Marker of the `usize` store layout: the number of bits of each encoded element.
+const USIZE_STORE_ELEMENT_BITS: u8 = 64;
+
+/// Encode the bits of `bits` as 64 bit elements, with the layout of a `BitVec<u64, O>`.
+///
+/// This doesn't depend on the type of the store, so the output is the same for a `usize` store
+/// on 32 and 64 bit platforms.
+fn encode_as_u64_elements<T: BitStore, O: BitOrder, W: Output + ?Sized>(
+	bits: &BitSlice<T, O>,
+	dest: &mut W,
+) {
+	for chunk in bits.chunks(64) {
+		let mut element = 0u64;
+		element.view_bits_mut::<O>()[..chunk.len()].clone_from_bitslice(chunk);
+		element.encode_to(dest);
+	}
+}
+
+// The width of `usize` depends on the platform, so a `usize` store is encoded with 64 bit
+// elements, after a marker byte giving that width and the compact bit length.
+impl<O: BitOrder> Encode for BitSlice<usize, O> {
+	fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+		dest.push_byte(USIZE_STORE_ELEMENT_BITS);
+		encode_bits_len(self.len(), dest);
+		encode_as_u64_elements(self, dest);
+	}
+}
+
+impl<O: BitOrder> Encode for BitVec<usize, O> {
 	fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
 		self.as_bitslice().encode_to(dest)
 	}
 }
 
-impl<O: BitOrder, T: BitStore + Encode> EncodeLike for BitBox<O, T> {}
+impl<O: BitOrder> EncodeLike for BitVec<usize, O> {}
 
-impl<O: BitOrder, T: BitStore + Decode> Decode for BitBox<O, T> {
+impl<O: BitOrder> Decode for BitVec<usize, O> {
 	fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
-		Ok(Self::from_bitslice(BitVec::<O, T>::decode(input)?.as_bitslice()))
+		if input.read_byte()? != USIZE_STORE_ELEMENT_BITS {
+			return Err("Invalid element width of a bitvec with `usize` store".into());
+		}
+
+		let bits = <Compact<u32>>::decode(input)?.0;
+		if bits as usize > ARCH32BIT_BITSLICE_MAX_BITS {
+			return Err("Attempt to decode a bitvec with too many bits".into());
+		}
+		let elements: Vec<u64> = decode_vec_with_len(input, required_elements::<u64>(bits)? as usize)?;
+
+		let mut result = Self::with_capacity(bits as usize);
+		for element in &elements {
+			let take = (bits as usize - result.len()).min(64);
+			result.extend_from_bitslice(&element.view_bits::<O>()[..take]);
+		}
+		Ok(result)
 	}
 }
 
 /// Calculates the number of element `T` required to store given amount of `bits` as if they were
-/// stored in `BitVec<_, T>`
+/// stored in `BitVec<T, _>`
 ///
 /// Returns an error if the number of bits + number of bits in element overflow u32 capacity.
 /// NOTE: this should never happen if `bits` is already checked to be less than
 /// `BitStore::MAX_BITS`.
 fn required_elements<T: BitStore>(bits: u32) -> Result<u32, Error> {
-	let element_bits = T::Mem::BITS as u32;
+	let element_bits = bits_of::<T::Mem>() as u32;
 	let error = Error::from("Attempt to decode bitvec with too many bits");
 	Ok((bits.checked_add(element_bits).ok_or_else(|| error)?  - 1) / element_bits)
 }
@@ -105,39 +288,39 @@ fn required_elements<T: BitStore>(bits: u32) -> Result<u32, Error> {
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use bitvec::{bitvec, order::Msb0};
+	use bitvec::{bitvec, bitarr, order::{Msb0, Lsb0}};
 	use crate::codec::MAX_PREALLOCATION;
 
 	macro_rules! test_data {
-		($inner_type:ident) => (
+		($order:ident, $inner_type:ident) => (
 			[
-				BitVec::<Msb0, $inner_type>::new(),
-				bitvec![Msb0, $inner_type; 0],
-				bitvec![Msb0, $inner_type; 1],
-				bitvec![Msb0, $inner_type; 0, 0],
-				bitvec![Msb0, $inner_type; 1, 0],
-				bitvec![Msb0, $inner_type; 0, 1],
-				bitvec![Msb0, $inner_type; 1, 1],
-				bitvec![Msb0, $inner_type; 1, 0, 1],
-				bitvec![Msb0, $inner_type; 0, 1, 0, 1, 0, 1, 1],
-				bitvec![Msb0, $inner_type; 0, 1, 0, 1, 0, 1, 1, 0],
-				bitvec![Msb0, $inner_type; 1, 1, 0, 1, 0, 1, 1, 0, 1],
-				bitvec![Msb0, $inner_type; 1, 0, 1, 0, 1, 1, 0, 0, 1, 0, 1, 0, 1, 1, 0],
-				bitvec![Msb0, $inner_type; 0, 1, 0, 1, 0, 1, 1, 0, 0, 1, 0, 1, 0, 1, 1, 0],
-				bitvec![Msb0, $inner_type; 0, 1, 0, 1, 0, 1, 1, 0, 0, 1, 0, 1, 0, 1, 1, 0, 0],
-				bitvec![Msb0, $inner_type; 0; 15],
-				bitvec![Msb0, $inner_type; 1; 16],
-				bitvec![Msb0, $inner_type; 0; 17],
-				bitvec![Msb0, $inner_type; 1; 31],
-				bitvec![Msb0, $inner_type; 0; 32],
-				bitvec![Msb0, $inner_type; 1; 33],
-				bitvec![Msb0, $inner_type; 0; 63],
-				bitvec![Msb0, $inner_type; 1; 64],
-				bitvec![Msb0, $inner_type; 0; 65],
-				bitvec![Msb0, $inner_type; 1; MAX_PREALLOCATION * 8 + 1],
-				bitvec![Msb0, $inner_type; 0; MAX_PREALLOCATION * 9],
-				bitvec![Msb0, $inner_type; 1; MAX_PREALLOCATION * 32 + 1],
-				bitvec![Msb0, $inner_type; 0; MAX_PREALLOCATION * 33],
+				BitVec::<$inner_type, $order>::new(),
+				bitvec![$inner_type, $order; 0],
+				bitvec![$inner_type, $order; 1],
+				bitvec![$inner_type, $order; 0, 0],
+				bitvec![$inner_type, $order; 1, 0],
+				bitvec![$inner_type, $order; 0, 1],
+				bitvec![$inner_type, $order; 1, 1],
+				bitvec![$inner_type, $order; 1, 0, 1],
+				bitvec![$inner_type, $order; 0, 1, 0, 1, 0, 1, 1],
+				bitvec![$inner_type, $order; 0, 1, 0, 1, 0, 1, 1, 0],
+				bitvec![$inner_type, $order; 1, 1, 0, 1, 0, 1, 1, 0, 1],
+				bitvec![$inner_type, $order; 1, 0, 1, 0, 1, 1, 0, 0, 1, 0, 1, 0, 1, 1, 0],
+				bitvec![$inner_type, $order; 0, 1, 0, 1, 0, 1, 1, 0, 0, 1, 0, 1, 0, 1, 1, 0],
+				bitvec![$inner_type, $order; 0, 1, 0, 1, 0, 1, 1, 0, 0, 1, 0, 1, 0, 1, 1, 0, 0],
+				bitvec![$inner_type, $order; 0; 15],
+				bitvec![$inner_type, $order; 1; 16],
+				bitvec![$inner_type, $order; 0; 17],
+				bitvec![$inner_type, $order; 1; 31],
+				bitvec![$inner_type, $order; 0; 32],
+				bitvec![$inner_type, $order; 1; 33],
+				bitvec![$inner_type, $order; 0; 63],
+				bitvec![$inner_type, $order; 1; 64],
+				bitvec![$inner_type, $order; 0; 65],
+				bitvec![$inner_type, $order; 1; MAX_PREALLOCATION * 8 + 1],
+				bitvec![$inner_type, $order; 0; MAX_PREALLOCATION * 9],
+				bitvec![$inner_type, $order; 1; MAX_PREALLOCATION * 32 + 1],
+				bitvec![$inner_type, $order; 0; MAX_PREALLOCATION * 33],
 			]
 		)
 	}
@@ -171,52 +354,332 @@ mod tests {
 
 	#[test]
 	fn bitvec_u8() {
-		for v in &test_data!(u8) {
+		for v in &test_data!(Msb0, u8) {
 			let encoded = v.encode();
-			assert_eq!(*v, BitVec::<Msb0, u8>::decode(&mut &encoded[..]).unwrap());
+			assert_eq!(*v, BitVec::<u8, Msb0>::decode(&mut &encoded[..]).unwrap());
 		}
 	}
 
 	#[test]
 	fn bitvec_u16() {
-		for v in &test_data!(u16) {
+		for v in &test_data!(Msb0, u16) {
 			let encoded = v.encode();
-			assert_eq!(*v, BitVec::<Msb0, u16>::decode(&mut &encoded[..]).unwrap());
+			assert_eq!(*v, BitVec::<u16, Msb0>::decode(&mut &encoded[..]).unwrap());
 		}
 	}
 
 	#[test]
 	fn bitvec_u32() {
-		for v in &test_data!(u32) {
+		for v in &test_data!(Msb0, u32) {
 			let encoded = v.encode();
-			assert_eq!(*v, BitVec::<Msb0, u32>::decode(&mut &encoded[..]).unwrap());
+			assert_eq!(*v, BitVec::<u32, Msb0>::decode(&mut &encoded[..]).unwrap());
 		}
 	}
 
 	#[test]
 	fn bitvec_u64() {
-		for v in &test_data!(u64) {
+		for v in &test_data!(Msb0, u64) {
 			let encoded = dbg!(v.encode());
-			assert_eq!(*v, BitVec::<Msb0, u64>::decode(&mut &encoded[..]).unwrap());
+			assert_eq!(*v, BitVec::<u64, Msb0>::decode(&mut &encoded[..]).unwrap());
+		}
+	}
+
+	#[test]
+	fn bitvec_lsb0_u8() {
+		for v in &test_data!(Lsb0, u8) {
+			let encoded = v.encode();
+			assert_eq!(*v, BitVec::<u8, Lsb0>::decode(&mut &encoded[..]).unwrap());
+		}
+	}
+
+	#[test]
+	fn bitvec_lsb0_u16() {
+		for v in &test_data!(Lsb0, u16) {
+			let encoded = v.encode();
+			assert_eq!(*v, BitVec::<u16, Lsb0>::decode(&mut &encoded[..]).unwrap());
 		}
 	}
 
+	#[test]
+	fn bitvec_lsb0_u32() {
+		for v in &test_data!(Lsb0, u32) {
+			let encoded = v.encode();
+			assert_eq!(*v, BitVec::<u32, Lsb0>::decode(&mut &encoded[..]).unwrap());
+		}
+	}
+
+	#[test]
+	fn bitvec_lsb0_u64() {
+		for v in &test_data!(Lsb0, u64) {
+			let encoded = v.encode();
+			assert_eq!(*v, BitVec::<u64, Lsb0>::decode(&mut &encoded[..]).unwrap());
+		}
+	}
+
+	#[test]
+	fn bitvec_layout_does_not_depend_on_platform_endianness() {
+		// The order is applied to the value of each element, which is then encoded little endian.
+		let lsb0 = bitvec![u16, Lsb0; 1, 0, 0, 0, 0, 0, 0, 0, 1];
+		assert_eq!(lsb0.encode(), vec![9 << 2, 0x01, 0x01]);
+		let msb0 = bitvec![u16, Msb0; 1, 0, 0, 0, 0, 0, 0, 0, 1];
+		assert_eq!(msb0.encode(), vec![9 << 2, 0x80, 0x80]);
+
+		let lsb0 = bitvec![u32, Lsb0; 0, 1, 1];
+		assert_eq!(lsb0.encode(), vec![3 << 2, 0b110, 0, 0, 0]);
+		assert_eq!(BitVec::<u32, Lsb0>::decode(&mut &[3 << 2, 0b110, 0, 0, 0][..]).unwrap(), lsb0);
+	}
+
+	#[test]
+	fn raw_elements_path_matches_rebuilt_elements() {
+		macro_rules! check {
+			($store:ident) => {
+				for v in test_data!(Msb0, $store).iter() {
+					assert_eq!(v.encode(), v.as_bitslice().encode());
+					assert_eq!(v.clone().into_boxed_bitslice().encode(), v.as_bitslice().encode());
+				}
+				for v in test_data!(Lsb0, $store).iter() {
+					assert_eq!(v.encode(), v.as_bitslice().encode());
+				}
+			};
+		}
+
+		check!(u8);
+		check!(u16);
+		check!(u32);
+		check!(u64);
+
+		// The unused bits of the last element are not encoded.
+		let mut v = BitVec::<u16, Msb0>::from_vec(vec![0xffff, 0xffff]);
+		v.truncate(19);
+		assert_eq!(v.encode(), vec![19 << 2, 0xff, 0xff, 0x00, 0xe0]);
+	}
+
+	#[test]
+	fn wire_format_for_all_store_widths() {
+		// 10 bits, `1` at positions 0, 3 and 9.
+		let bools = [true, false, false, true, false, false, false, false, false, true];
+
+		macro_rules! check {
+			($store:ty, $order:ty, $expected:expr) => {{
+				let v: BitVec<$store, $order> = bools.iter().copied().collect();
+				let mut expected = vec![10 << 2];
+				expected.extend_from_slice(&$expected);
+				assert_eq!(v.encode(), expected, stringify!($store, $order));
+				assert_eq!(BitVec::<$store, $order>::decode(&mut &expected[..]).unwrap(), v);
+			}};
+		}
+
+		check!(u8, Lsb0, [0b0000_1001, 0b0000_0010]);
+		check!(u8, Msb0, [0b1001_0000, 0b0100_0000]);
+		check!(u16, Lsb0, 0b10_0000_1001u16.to_le_bytes());
+		check!(u16, Msb0, 0b1001_0000_0100_0000u16.to_le_bytes());
+		check!(u32, Lsb0, 0b10_0000_1001u32.to_le_bytes());
+		check!(u32, Msb0, (0b1001_0000_0100_0000u32 << 16).to_le_bytes());
+		check!(u64, Lsb0, 0b10_0000_1001u64.to_le_bytes());
+		check!(u64, Msb0, (0b1001_0000_0100_0000u64 << 48).to_le_bytes());
+	}
+
+	#[test]
+	fn malformed_bit_vec_is_an_error() {
+		// Bit counts whose elements are missing or incomplete.
+		for bits in [1u32, 8, 9, 63, 64, 65, ARCH32BIT_BITSLICE_MAX_BITS as u32] {
+			let mut encoded = Compact(bits).encode();
+			encoded.push(0xff);
+
+			// A single byte of data is only enough for a `BitVec<u8, _>` of at most 8 bits.
+			assert_eq!(BitVec::<u8, Msb0>::decode(&mut &encoded[..]).is_err(), bits > 8);
+			assert!(BitVec::<u16, Lsb0>::decode(&mut &encoded[..]).is_err());
+			assert!(BitVec::<u64, Lsb0>::decode(&mut &encoded[..]).is_err());
+			assert!(BitBox::<u32, Msb0>::decode(&mut &encoded[..]).is_err());
+
+			let mut encoded_usize = vec![USIZE_STORE_ELEMENT_BITS];
+			encoded_usize.extend_from_slice(&encoded);
+			assert!(BitVec::<usize, Lsb0>::decode(&mut &encoded_usize[..]).is_err());
+		}
+
+		// Bit counts too large to be stored.
+		let too_many = Compact(ARCH32BIT_BITSLICE_MAX_BITS as u32 + 1).encode();
+		assert!(BitVec::<u8, Msb0>::decode(&mut &too_many[..]).is_err());
+		let too_many = Compact(u32::max_value()).encode();
+		assert!(BitVec::<u32, Lsb0>::decode(&mut &too_many[..]).is_err());
+		assert!(decode_bitslice::<Msb0>(&mut &too_many[..]).is_err());
+
+		// Wrong number of bits for a `BitArray`.
+		assert!(BitArray::<[u8; 2], Lsb0>::decode(&mut &[15 << 2, 0, 0][..]).is_err());
+	}
+
+	#[test]
+	fn try_encode_bitslice_works() {
+		let bits = bitvec![u16, Msb0; 1, 0, 1, 1, 0, 0, 1, 0, 1];
+		assert_eq!(try_encode_bitslice(&bits), Ok(bits.encode()));
+		assert_eq!(try_encode_bitslice(&bits[1..]), Ok(bits[1..].encode()));
+
+		let bits = bitvec![usize, Lsb0; 1; 70];
+		assert_eq!(try_encode_bitslice(&bits), Ok(bits.encode()));
+	}
+
+	// A bitslice with more than `u32::max_value()` bits would take 512 MiB, so only the length
+	// check is exercised.
+	#[test]
+	#[cfg(target_pointer_width = "64")]
+	fn too_many_bits_is_an_error() {
+		assert_eq!(check_bits_len(u32::max_value() as usize), Ok(u32::max_value()));
+		assert!(check_bits_len(u32::max_value() as usize + 1).is_err());
+		assert!(check_bits_len(usize::max_value()).is_err());
+	}
+
+	#[test]
+	fn truncated_element_is_rejected() {
+		// The data of a `BitVec<u16, _>` of 17 bits is two `u16`, three bytes is not enough.
+		let encoded = bitvec![u16, Msb0; 1; 17].encode();
+		assert_eq!(encoded.len(), 1 + 4);
+		assert!(BitVec::<u16, Msb0>::decode(&mut &encoded[..4]).is_err());
+		assert!(BitVec::<u64, Lsb0>::decode(&mut &bitvec![u64, Lsb0; 1; 65].encode()[..16]).is_err());
+
+		// Decoding only consumes whole elements, the trailing byte is left in the input.
+		let mut input = &[8 << 2, 0xff, 0xff, 0xff][..];
+		assert_eq!(BitVec::<u16, Lsb0>::decode(&mut input).unwrap(), bitvec![u16, Lsb0; 1; 8]);
+		assert_eq!(input, &[0xff]);
+	}
+
 	#[test]
 	fn bitslice() {
 		let data: &[u8] = &[0x69];
-		let slice = BitSlice::<Msb0, u8>::from_slice(data).unwrap();
+		let slice = BitSlice::<u8, Msb0>::from_slice(data);
 		let encoded = slice.encode();
-		let decoded = BitVec::<Msb0, u8>::decode(&mut &encoded[..]).unwrap();
+		let decoded = BitVec::<u8, Msb0>::decode(&mut &encoded[..]).unwrap();
 		assert_eq!(slice, decoded.as_bitslice());
 	}
 
 	#[test]
 	fn bitbox() {
 		let data: &[u8] = &[5, 10];
-		let slice = BitSlice::<Msb0, u8>::from_slice(data).unwrap();
-		let bb = BitBox::<Msb0, u8>::from_bitslice(slice);
+		let slice = BitSlice::<u8, Msb0>::from_slice(data);
+		let bb = BitBox::<u8, Msb0>::from_bitslice(slice);
 		let encoded = bb.encode();
-		let decoded = BitBox::<Msb0, u8>::decode(&mut &encoded[..]).unwrap();
+		let decoded = BitBox::<u8, Msb0>::decode(&mut &encoded[..]).unwrap();
 		assert_eq!(bb, decoded);
 	}
+
+	#[test]
+	fn unaligned_bitslice_encodes_like_bitvec() {
+		let data = [0b1010_1100u8, 0b0111_0001, 0xff];
+		let bits = data.view_bits::<Msb0>();
+		for start in 0..8 {
+			for end in start..=20 {
+				let slice = &bits[start..end];
+				let encoded = slice.encode();
+				assert_eq!(encoded, slice.to_bitvec().encode());
+				assert_eq!(BitVec::<u8, Msb0>::decode(&mut &encoded[..]).unwrap(), slice);
+			}
+		}
+
+		// Bits of the last element outside of the slice are not encoded.
+		assert_eq!(bits[..3].encode(), vec![3 << 2, 0b1010_0000]);
+	}
+
+	#[test]
+	fn usize_store_layout_is_platform_independent() {
+		fn check<O: BitOrder>(bools: &[bool]) {
+			let as_u32: BitVec<u32, O> = bools.iter().copied().collect();
+			let as_u64: BitVec<u64, O> = bools.iter().copied().collect();
+			let as_usize: BitVec<usize, O> = bools.iter().copied().collect();
+
+			// 32 and 64 bit `usize` elements hold the bits differently, but are encoded the same.
+			let mut from_u32 = Vec::new();
+			encode_as_u64_elements(&as_u32, &mut from_u32);
+			let mut from_u64 = Vec::new();
+			encode_as_u64_elements(&as_u64, &mut from_u64);
+			assert_eq!(from_u32, from_u64);
+
+			let mut expected = vec![USIZE_STORE_ELEMENT_BITS];
+			expected.extend(as_u64.encode());
+			let encoded = as_usize.encode();
+			assert_eq!(encoded, expected);
+			assert_eq!(BitVec::<usize, O>::decode(&mut &encoded[..]).unwrap(), as_usize);
+		}
+
+		for len in &[0, 1, 31, 32, 33, 63, 64, 65, 200] {
+			let bools: Vec<bool> = (0..*len).map(|i| i % 3 == 0 || i % 7 == 0).collect();
+			check::<Lsb0>(&bools);
+			check::<Msb0>(&bools);
+		}
+	}
+
+	#[test]
+	fn usize_store_rejects_other_widths() {
+		let mut encoded = bitvec![usize, Lsb0; 1, 0, 1].encode();
+		assert_eq!(encoded[..2], [64, 3 << 2]);
+		encoded[0] = 32;
+		assert!(BitVec::<usize, Lsb0>::decode(&mut &encoded[..]).is_err());
+	}
+
+	#[test]
+	fn decoded_bitslice_borrows_from_input() {
+		for v in &test_data!(Msb0, u8) {
+			let mut encoded = v.encode();
+			encoded.push(0xaa);
+			let mut input = &encoded[..];
+
+			let decoded = decode_bitslice::<Msb0>(&mut input).unwrap();
+			assert_eq!(decoded, v.as_bitslice());
+			assert_eq!(input, &[0xaa]);
+
+			if !decoded.is_empty() {
+				let prefix_len = encoded.len() - 1 - v.as_raw_slice().len();
+				assert_eq!(decoded.as_bitptr().pointer(), encoded[prefix_len..].as_ptr());
+			}
+		}
+
+		let encoded = bitvec![u8, Lsb0; 1; 9].encode();
+		assert!(decode_bitslice::<Lsb0>(&mut &encoded[..2]).is_err());
+	}
+
+	#[test]
+	fn bitarray_roundtrip() {
+		let array = bitarr![u16, Lsb0; 1, 0, 1, 1, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 1];
+		let encoded = array.encode();
+		assert_eq!(encoded.len(), array.size_hint());
+		assert_eq!(encoded, array.as_bitslice().encode());
+		assert_eq!(BitArray::<[u16; 2], Lsb0>::decode(&mut &encoded[..]).unwrap(), array);
+		assert_eq!(BitVec::<u16, Lsb0>::decode(&mut &encoded[..]).unwrap(), array.as_bitslice());
+
+		let array = BitArray::<u32, Msb0>::new(0x8000_0001);
+		let encoded = array.encode();
+		assert_eq!(encoded, vec![32 << 2, 0x01, 0, 0, 0x80]);
+		assert_eq!(BitArray::<u32, Msb0>::decode(&mut &encoded[..]).unwrap(), array);
+	}
+
+	#[test]
+	fn bitarray_rejects_wrong_length() {
+		let encoded = bitvec![u8, Msb0; 1; 7].encode();
+		assert!(BitArray::<u8, Msb0>::decode(&mut &encoded[..]).is_err());
+
+		let encoded = bitvec![u8, Msb0; 1; 16].encode();
+		assert!(BitArray::<u8, Msb0>::decode(&mut &encoded[..]).is_err());
+		assert!(BitArray::<[u8; 2], Msb0>::decode(&mut &encoded[..]).is_ok());
+	}
+
+	#[test]
+	#[cfg(feature = "max-encoded-len")]
+	fn bitarray_max_encoded_len() {
+		use crate::MaxEncodedLen;
+
+		assert_eq!(BitArray::<u8, Lsb0>::max_encoded_len(), 1 + 1);
+		assert_eq!(BitArray::<[u64; 2], Lsb0>::max_encoded_len(), 2 + 16);
+		let array = BitArray::<[u64; 2], Lsb0>::ZERO;
+		assert_eq!(array.encode().len(), BitArray::<[u64; 2], Lsb0>::max_encoded_len());
+
+		fn max_of<A: BitViewSized + crate::MaxEncodedLen, O: BitOrder>(_: &BitArray<A, O>) -> usize {
+			BitArray::<A, O>::max_encoded_len()
+		}
+
+		// `bitarr!` rounds the bits up to whole elements, which are all encoded.
+		let array = bitarr![u8, Msb0; 1; 20];
+		assert_eq!(max_of(&array), 1 + 3);
+		assert_eq!(array.encode().len(), max_of(&array));
+		let array = bitarr![u32, Lsb0; 1; 100];
+		assert_eq!(max_of(&array), 2 + 16);
+		assert_eq!(array.encode().len(), max_of(&array));
+	}
 }
@@ -0,0 +1,175 @@
+// Copyright 2021 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Codec implementations for C-like enums which can't derive them.
+
+/// Implement `Encode`, `EncodeLike` and `Decode` for a C-like enum, given the byte each variant is
+/// encoded as. With the `max-encoded-len` feature, `MaxEncodedLen` is implemented too.
+///
+/// This is meant for enums defined in other crates; enums of your own crate can just derive the
+/// traits. Every variant must be listed, and decoding any other byte fails.
+///
+/// ```
+/// # use parity_scale_codec::{Encode, Decode, impl_c_like_enum_codec};
+/// #[derive(Debug, PartialEq)]
+/// pub enum Direction {
+///     North,
+///     East,
+///     South,
+///     West,
+/// }
+///
+/// impl_c_like_enum_codec!(Direction { North => 0, East => 1, South => 2, West => 3 });
+///
+/// assert_eq!(Direction::South.encode(), vec![2]);
+/// assert_eq!(Direction::decode(&mut &[3][..]), Ok(Direction::West));
+/// assert!(Direction::decode(&mut &[4][..]).is_err());
+/// ```
+#[macro_export]
+macro_rules! impl_c_like_enum_codec {
+	( $ty:ty { $( $variant:ident => $index:expr ),* $(,)? } ) => {
+		impl $crate::Encode for $ty {
+			fn size_hint(&self) -> usize {
+				1
+			}
+
+			fn encode_to<W: $crate::Output + ?Sized>(&self, dest: &mut W) {
+				let index: u8 = match *self {
+					$( <$ty>::$variant => $index, )*
+				};
+				dest.push_byte(index)
+			}
+		}
+
+		impl $crate::EncodeLike for $ty {}
+
+		impl $crate::Decode for $ty {
+			fn decode<I: $crate::Input>(input: &mut I) -> ::core::result::Result<Self, $crate::Error> {
+				let index = input.read_byte()
+					.map_err(|e| e.chain(concat!("Could not decode variant index of `", stringify!($ty), "`")))?;
+
+				match index {
+					$( x if x == $index => ::core::result::Result::Ok(<$ty>::$variant), )*
+					_ => ::core::result::Result::Err(
+						concat!("Could not decode `", stringify!($ty), "`, variant doesn't exist").into()
+					),
+				}
+			}
+		}
+
+		$crate::__impl_c_like_enum_max_encoded_len!($ty);
+	};
+}
+
+#[cfg(feature = "max-encoded-len")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __impl_c_like_enum_max_encoded_len {
+	( $ty:ty ) => {
+		impl $crate::MaxEncodedLen for $ty {
+			fn max_encoded_len() -> usize {
+				1
+			}
+		}
+	};
+}
+
+#[cfg(not(feature = "max-encoded-len"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __impl_c_like_enum_max_encoded_len {
+	( $ty:ty ) => {};
+}
+
+impl_c_like_enum_codec!(core::num::FpCategory {
+	Nan => 0,
+	Infinite => 1,
+	Zero => 2,
+	Subnormal => 3,
+	Normal => 4,
+});
+
+impl_c_like_enum_codec!(core::fmt::Alignment {
+	Left => 0,
+	Right => 1,
+	Center => 2,
+});
+
+#[cfg(feature = "std")]
+impl_c_like_enum_codec!(std::net::Shutdown {
+	Read => 0,
+	Write => 1,
+	Both => 2,
+});
+
+#[cfg(test)]
+mod tests {
+	use crate::{Encode, Decode};
+	use core::num::FpCategory;
+
+	#[derive(Debug, PartialEq, Clone, Copy)]
+	enum Status {
+		Idle,
+		Busy,
+		Failed,
+	}
+
+	impl_c_like_enum_codec!(Status {
+		Idle => 1,
+		Busy => 2,
+		Failed => 0xff,
+	});
+
+	#[test]
+	fn custom_enum_uses_listed_indices() {
+		for &(status, index) in &[(Status::Idle, 1u8), (Status::Busy, 2), (Status::Failed, 0xff)] {
+			assert_eq!(status.encode(), vec![index]);
+			assert_eq!(Status::decode(&mut &[index][..]), Ok(status));
+		}
+
+		assert!(Status::decode(&mut &[0][..]).is_err());
+		assert!(Status::decode(&mut &[3][..]).is_err());
+		assert!(Status::decode(&mut &[][..]).is_err());
+	}
+
+	#[test]
+	fn fp_category_roundtrip() {
+		for value in &[f64::NAN, f64::INFINITY, 0.0, f64::MIN_POSITIVE / 2.0, 1.0] {
+			let category = value.classify();
+			assert_eq!(FpCategory::decode(&mut &category.encode()[..]), Ok(category));
+		}
+
+		assert_eq!(f32::NAN.classify().encode(), vec![0]);
+		assert_eq!(1.0f32.classify().encode(), vec![4]);
+		assert!(FpCategory::decode(&mut &[5][..]).is_err());
+	}
+
+	#[test]
+	#[cfg(feature = "std")]
+	fn shutdown_roundtrip() {
+		use std::net::Shutdown;
+
+		assert_eq!(Shutdown::Both.encode(), vec![2]);
+		assert_eq!(Shutdown::decode(&mut &[1][..]), Ok(Shutdown::Write));
+	}
+
+	#[test]
+	#[cfg(feature = "max-encoded-len")]
+	fn c_like_enum_max_length() {
+		use crate::MaxEncodedLen;
+
+		assert_eq!(Status::max_encoded_len(), 1);
+		assert_eq!(FpCategory::max_encoded_len(), 1);
+	}
+}
@@ -0,0 +1,149 @@
+// Copyright 2021 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{Error, Input};
+
+/// Input that reads from several byte segments as if they were concatenated.
+///
+/// Values may straddle segment boundaries, so a payload received in multiple buffers can be
+/// decoded without copying it into a single buffer first.
+///
+/// ```
+/// # use parity_scale_codec::{ChainedInput, Decode};
+/// let segments = [vec![1, 0], vec![0, 0, 4]];
+/// let mut input = ChainedInput::new(&segments);
+/// assert_eq!(<(u32, u8)>::decode(&mut input), Ok((1, 4)));
+/// ```
+pub struct ChainedInput<'a, S> {
+	segments: &'a [S],
+	current: &'a [u8],
+	remaining: usize,
+}
+
+// Not derived, as the segments are borrowed `S` doesn't need to be `Clone`.
+impl<'a, S> Clone for ChainedInput<'a, S> {
+	fn clone(&self) -> Self {
+		Self { segments: self.segments, current: self.current, remaining: self.remaining }
+	}
+}
+
+impl<'a, S: AsRef<[u8]>> ChainedInput<'a, S> {
+	/// Create a new instance reading the given segments in order.
+	pub fn new(segments: &'a [S]) -> Self {
+		let remaining = segments.iter().map(|s| s.as_ref().len()).sum();
+		Self { segments, current: &[], remaining }
+	}
+
+	/// Make `current` the next non-empty segment, if `current` is exhausted.
+	fn next_segment(&mut self) {
+		while self.current.is_empty() {
+			match self.segments.split_first() {
+				Some((first, rest)) => {
+					self.current = first.as_ref();
+					self.segments = rest;
+				},
+				None => return,
+			}
+		}
+	}
+}
+
+impl<'a, S: AsRef<[u8]>> Input for ChainedInput<'a, S> {
+	fn remaining_len(&mut self) -> Result<Option<usize>, Error> {
+		Ok(Some(self.remaining))
+	}
+
+	fn read(&mut self, into: &mut [u8]) -> Result<(), Error> {
+		if into.len() > self.remaining {
+			return Err("Not enough data to fill buffer".into());
+		}
+
+		let mut filled = 0;
+		while filled < into.len() {
+			self.next_segment();
+			let len = self.current.len().min(into.len() - filled);
+			into[filled..filled + len].copy_from_slice(&self.current[..len]);
+			self.current = &self.current[len..];
+			filled += len;
+		}
+		self.remaining -= into.len();
+
+		Ok(())
+	}
+
+	fn read_byte(&mut self) -> Result<u8, Error> {
+		self.next_segment();
+		let (byte, rest) = self.current.split_first().ok_or("Not enough data to fill buffer")?;
+		self.current = rest;
+		self.remaining -= 1;
+
+		Ok(*byte)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{Compact, Decode, Encode};
+
+	#[test]
+	fn values_straddle_segment_boundaries() {
+		let value = (0x0102_0304u32, vec![5u8; 10], Compact(1u64 << 40), 0x0708u16);
+		let encoded = value.encode();
+
+		for split in 0..=encoded.len() {
+			let segments = [&encoded[..split], &[][..], &encoded[split..]];
+			let mut input = ChainedInput::new(&segments);
+			assert_eq!(input.remaining_len(), Ok(Some(encoded.len())));
+			assert_eq!(Decode::decode(&mut input), Ok(value.clone()));
+			assert_eq!(input.remaining_len(), Ok(Some(0)));
+		}
+	}
+
+	#[test]
+	fn many_small_segments() {
+		let value = (u64::max_value(), vec![1u32, 2, 3]);
+		let segments: Vec<Vec<u8>> = value.encode().into_iter().map(|b| vec![b]).collect();
+
+		let mut input = ChainedInput::new(&segments);
+		assert_eq!(Decode::decode(&mut input), Ok(value));
+		assert!(input.read_byte().is_err());
+	}
+
+	#[test]
+	fn not_enough_data() {
+		let segments = [vec![1u8, 2], vec![3]];
+		let mut input = ChainedInput::new(&segments);
+		assert!(u32::decode(&mut input).is_err());
+
+		let mut input = ChainedInput::new(&segments);
+		let mut buf = [0u8; 3];
+		input.read(&mut buf).unwrap();
+		assert_eq!(buf, [1, 2, 3]);
+		assert_eq!(input.remaining_len(), Ok(Some(0)));
+
+		let empty: [&[u8]; 0] = [];
+		assert!(ChainedInput::new(&empty).read_byte().is_err());
+	}
+
+	#[test]
+	fn failed_scan_rewinds_across_segments() {
+		let segments = [vec![1u8], vec![2, 3], vec![4]];
+		let mut input = ChainedInput::new(&segments);
+
+		assert!(crate::scan(&mut input, |i| <(u16, u32)>::decode(i)).is_err());
+		assert_eq!(input.remaining_len(), Ok(Some(4)));
+		assert_eq!(u32::decode(&mut input), Ok(0x0403_0201));
+	}
+}
@@ -0,0 +1,107 @@
+// Copyright 2021 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! `chrono` specific serialization.
+//!
+//! A `NaiveDateTime` is encoded as its number of seconds since the Unix epoch (`i64`) followed by
+//! its nanoseconds within that second (`u32`). The nanoseconds exceed one second only during a
+//! leap second.
+
+use chrono::{DateTime, NaiveDateTime};
+
+use crate::{Encode, Decode, Input, Output, Error};
+use crate::encode_like::EncodeLike;
+
+/// Nanoseconds are below `2 * NANOS_PER_SEC`, the second half being used by leap seconds.
+const MAX_NANOS: u32 = 2_000_000_000;
+
+impl Encode for NaiveDateTime {
+	fn size_hint(&self) -> usize {
+		8 + 4
+	}
+
+	fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+		let utc = self.and_utc();
+		utc.timestamp().encode_to(dest);
+		utc.timestamp_subsec_nanos().encode_to(dest);
+	}
+}
+
+impl EncodeLike for NaiveDateTime {}
+
+impl Decode for NaiveDateTime {
+	fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+		let secs = i64::decode(input)?;
+		let nanos = u32::decode(input)?;
+		if nanos >= MAX_NANOS {
+			return Err("`NaiveDateTime` nanoseconds out of range".into());
+		}
+
+		DateTime::from_timestamp(secs, nanos)
+			.map(|utc| utc.naive_utc())
+			.ok_or_else(|| "`NaiveDateTime` timestamp out of range".into())
+	}
+
+	fn encoded_fixed_size() -> Option<usize> {
+		Some(8 + 4)
+	}
+}
+
+#[cfg(feature = "max-encoded-len")]
+impl crate::MaxEncodedLen for NaiveDateTime {
+	fn max_encoded_len() -> usize {
+		8 + 4
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use chrono::NaiveDate;
+
+	#[test]
+	fn naive_date_time_roundtrip() {
+		let value = NaiveDate::from_ymd_opt(2021, 7, 1)
+			.unwrap()
+			.and_hms_nano_opt(12, 30, 15, 123_456_789)
+			.unwrap();
+
+		let encoded = value.encode();
+		assert_eq!(encoded.len(), value.size_hint());
+		assert_eq!(encoded, (1_625_142_615i64, 123_456_789u32).encode());
+		assert_eq!(NaiveDateTime::decode(&mut &encoded[..]), Ok(value));
+
+		let before_epoch = NaiveDate::from_ymd_opt(1969, 12, 31).unwrap().and_hms_opt(23, 59, 59).unwrap();
+		assert_eq!(before_epoch.encode(), (-1i64, 0u32).encode());
+		assert_eq!(NaiveDateTime::decode(&mut &before_epoch.encode()[..]), Ok(before_epoch));
+	}
+
+	#[test]
+	fn naive_date_time_leap_second_roundtrip() {
+		let leap = NaiveDate::from_ymd_opt(2016, 12, 31)
+			.unwrap()
+			.and_hms_milli_opt(23, 59, 59, 1_500)
+			.unwrap();
+		assert_eq!(NaiveDateTime::decode(&mut &leap.encode()[..]), Ok(leap));
+	}
+
+	#[test]
+	fn naive_date_time_rejects_invalid_values() {
+		assert!(NaiveDateTime::decode(&mut &(0i64, MAX_NANOS).encode()[..]).is_err());
+		assert!(NaiveDateTime::decode(&mut &(0i64, u32::max_value()).encode()[..]).is_err());
+		// Leap second nanoseconds are only valid on the last second of a minute.
+		assert!(NaiveDateTime::decode(&mut &(0i64, 1_500_000_000u32).encode()[..]).is_err());
+		assert!(NaiveDateTime::decode(&mut &(i64::max_value(), 0u32).encode()[..]).is_err());
+	}
+}
@@ -16,11 +16,11 @@
 
 use core::fmt;
 use core::{
-	convert::TryFrom,
+	convert::{Infallible, TryFrom},
 	iter::FromIterator,
-	marker::PhantomData,
+	marker::{PhantomData, PhantomPinned},
 	mem,
-	ops::{Deref, Range, RangeInclusive},
+	ops::{ControlFlow, Deref, Range, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive},
 	time::Duration,
 };
 use core::num::{
@@ -34,6 +34,7 @@ use core::num::{
 	NonZeroU32,
 	NonZeroU64,
 	NonZeroU128,
+	Wrapping,
 };
 use arrayvec::ArrayVec;
 
@@ -73,9 +74,17 @@ pub trait Input {
 	/// Read the exact number of bytes required to fill the given buffer.
 	///
 	/// Note that this function is similar to `std::io::Read::read_exact` and not
-	/// `std::io::Read::read`.
+	/// `std::io::Read::read`. Implementations must either fill the whole buffer or
+	/// return an error.
 	fn read(&mut self, into: &mut [u8]) -> Result<(), Error>;
 
+	/// Decode exactly `into.len()` bytes into the given borrowed buffer, or return an error.
+	///
+	/// This avoids going through an intermediate `Vec` when decoding into fixed buffers.
+	fn decode_bytes_into(&mut self, into: &mut [u8]) -> Result<(), Error> {
+		self.read(into)
+	}
+
 	/// Read a single byte from the input.
 	fn read_byte(&mut self) -> Result<u8, Error> {
 		let mut buf = [0u8];
@@ -87,6 +96,9 @@ pub trait Input {
 	/// This is called when decoding a new refence-based instance,
 	/// such as `Vec` or `Box`. Currently all such types are
 	/// allocated on the heap.
+	///
+	/// Collections (`Vec`, `VecDeque`, `LinkedList`, `BinaryHeap`, the maps and the sets) call it
+	/// before decoding their elements, unless the elements are primitive numbers read in bulk.
 	fn descend_ref(&mut self) -> Result<(), Error> {
 		Ok(())
 	}
@@ -94,6 +106,15 @@ pub trait Input {
 	/// Ascend to previous structure level when decoding.
 	/// This is called when decoding reference-based type is finished.
 	fn ascend_ref(&mut self) {}
+
+	/// !INTERNAL USE ONLY!
+	///
+	/// Decodes a `bytes::Bytes`, inputs backed by `Bytes` override this to avoid the copy.
+	#[cfg(feature = "bytes")]
+	#[doc(hidden)]
+	fn scale_internal_decode_bytes(&mut self) -> Result<bytes::Bytes, Error> where Self: Sized {
+		Vec::<u8>::decode(self).map(bytes::Bytes::from)
+	}
 }
 
 impl<'a> Input for &'a [u8] {
@@ -112,6 +133,28 @@ impl<'a> Input for &'a [u8] {
 	}
 }
 
+/// Run `f` on a copy of `input`, and only advance `input` if `f` succeeds.
+///
+/// On failure `input` is left untouched, so another format can be tried on the same bytes. This
+/// works with any input which can be cloned cheaply, like `&[u8]`.
+///
+/// ```
+/// # use parity_scale_codec::{Decode, scan};
+/// let mut input = &[1, 2, 3][..];
+/// assert!(scan(&mut input, |i| <[u8; 4]>::decode(i)).is_err());
+/// assert_eq!(scan(&mut input, |i| u16::decode(i)), Ok(0x0201));
+/// assert_eq!(input, &[3]);
+/// ```
+pub fn scan<I, T, F>(input: &mut I, f: F) -> Result<T, Error> where
+	I: Input + Clone,
+	F: FnOnce(&mut I) -> Result<T, Error>,
+{
+	let mut attempt = input.clone();
+	let res = f(&mut attempt)?;
+	*input = attempt;
+	Ok(res)
+}
+
 #[cfg(feature = "std")]
 impl From<std::io::Error> for Error {
 	fn from(err: std::io::Error) -> Self {
@@ -164,6 +207,19 @@ pub trait Output {
 	fn push_byte(&mut self, byte: u8) {
 		self.write(&[byte]);
 	}
+
+	/// Write `byte` to the output `count` times.
+	///
+	/// The default implementation writes the bytes in chunks from a small buffer.
+	fn write_byte_repeated(&mut self, byte: u8, count: usize) {
+		let chunk = [byte; 64];
+		let mut remaining = count;
+		while remaining > 0 {
+			let len = remaining.min(chunk.len());
+			self.write(&chunk[..len]);
+			remaining -= len;
+		}
+	}
 }
 
 #[cfg(not(feature = "std"))]
@@ -171,6 +227,10 @@ impl Output for Vec<u8> {
 	fn write(&mut self, bytes: &[u8]) {
 		self.extend_from_slice(bytes)
 	}
+
+	fn write_byte_repeated(&mut self, byte: u8, count: usize) {
+		self.resize(self.len() + count, byte)
+	}
 }
 
 #[cfg(feature = "std")]
@@ -178,6 +238,14 @@ impl<W: std::io::Write> Output for W {
 	fn write(&mut self, bytes: &[u8]) {
 		(self as &mut dyn std::io::Write).write_all(bytes).expect("Codec outputs are infallible");
 	}
+
+	fn write_byte_repeated(&mut self, byte: u8, count: usize) {
+		use std::io::Read;
+
+		let copied = std::io::copy(&mut std::io::repeat(byte).take(count as u64), self)
+			.expect("Codec outputs are infallible");
+		debug_assert_eq!(copied, count as u64);
+	}
 }
 
 
@@ -249,6 +317,28 @@ pub trait Encode {
 		self.encode_to(&mut size_tracker);
 		size_tracker.written
 	}
+
+	/// Encode self into the given slice, returning the number of bytes written.
+	///
+	/// Returns an error if `buf` is too small to hold the whole encoding. In that case the
+	/// contents of `buf` are unspecified. No buffer is allocated by the output itself, which
+	/// makes this usable where [`Encode::encode`] is not.
+	fn encode_to_slice(&self, buf: &mut [u8]) -> Result<usize, Error> {
+		let mut output = SliceOutput { buf, written: 0, overflow: false };
+		self.encode_to(&mut output);
+		if output.overflow {
+			return Err("Destination buffer is too small for the encoded value".into())
+		}
+		Ok(output.written)
+	}
+
+	/// Convert self to its encoding written as base64.
+	///
+	/// The standard alphabet of RFC 4648 is used, with `=` padding.
+	#[cfg(feature = "base64")]
+	fn encode_base64(&self) -> crate::alloc::string::String {
+		crate::base64::encode(&self.encode())
+	}
 }
 
 // Implements `Output` and only keeps track of the number of written bytes
@@ -264,6 +354,29 @@ impl Output for SizeTracker {
 	fn push_byte(&mut self, _byte: u8) {
 		self.written += 1;
 	}
+
+	fn write_byte_repeated(&mut self, _byte: u8, count: usize) {
+		self.written += count;
+	}
+}
+
+// Implements `Output` on top of a borrowed slice, recording whether a write did not fit
+struct SliceOutput<'a> {
+	buf: &'a mut [u8],
+	written: usize,
+	overflow: bool,
+}
+
+impl<'a> Output for SliceOutput<'a> {
+	fn write(&mut self, bytes: &[u8]) {
+		match self.buf.get_mut(self.written..self.written + bytes.len()) {
+			Some(dest) if !self.overflow => {
+				dest.copy_from_slice(bytes);
+				self.written += bytes.len();
+			},
+			_ => self.overflow = true,
+		}
+	}
 }
 
 /// Trait that allows the length of a collection to be read, without having
@@ -300,6 +413,35 @@ pub trait Decode: Sized {
 	fn encoded_fixed_size() -> Option<usize> {
 		None
 	}
+
+	/// Decode the value from the bytes written as hexadecimal in `hex`, see [`from_hex`].
+	///
+	/// All the bytes must be consumed, otherwise an error is returned.
+	///
+	/// ```
+	/// # use parity_scale_codec::Decode;
+	/// assert_eq!(u32::decode_hex("01000000"), Ok(1));
+	/// ```
+	///
+	/// [`from_hex`]: crate::from_hex
+	fn decode_hex(hex: &str) -> Result<Self, Error> {
+		crate::DecodeAll::decode_all(&crate::hex::from_hex(hex)?)
+	}
+
+	/// Decode the value from its encoding written as base64, see [`Encode::encode_base64`].
+	///
+	/// The string must be padded and can't contain whitespace. All the bytes must be consumed,
+	/// otherwise an error is returned.
+	///
+	/// ```
+	/// # use parity_scale_codec::{Decode, Encode};
+	/// assert_eq!(u32::decode_base64("AQAAAA=="), Ok(1));
+	/// assert_eq!(1u32.encode_base64(), "AQAAAA==");
+	/// ```
+	#[cfg(feature = "base64")]
+	fn decode_base64(base64: &str) -> Result<Self, Error> {
+		crate::DecodeAll::decode_all(&crate::base64::decode(base64)?)
+	}
 }
 
 /// Trait that allows zero-copy read/write of value-references to/from slices in LE format.
@@ -365,6 +507,87 @@ mod feature_full_wrapper_type_encode {
 	impl EncodeLike for String {}
 	impl EncodeLike<&str> for String {}
 	impl EncodeLike<String> for &str {}
+	impl EncodeLike<Box<str>> for String {}
+	impl EncodeLike<Box<str>> for &str {}
+	impl EncodeLike<String> for Box<str> {}
+	impl EncodeLike<&str> for Box<str> {}
+	impl EncodeLike<String> for Cow<'_, str> {}
+	impl EncodeLike<&str> for Cow<'_, str> {}
+	impl EncodeLike<Cow<'_, str>> for String {}
+	impl EncodeLike<Cow<'_, str>> for &str {}
+}
+
+#[cfg(feature = "bytes")]
+mod feature_wrapper_bytes {
+	use super::*;
+	use bytes::{Bytes, BytesMut};
+
+	impl WrapperTypeEncode for Bytes {}
+	impl EncodeLike for Bytes {}
+	impl EncodeLike<&[u8]> for Bytes {}
+	impl EncodeLike<Vec<u8>> for Bytes {}
+	impl EncodeLike<Bytes> for &[u8] {}
+	impl EncodeLike<Bytes> for Vec<u8> {}
+
+	impl WrapperTypeEncode for BytesMut {}
+	impl EncodeLike for BytesMut {}
+	impl EncodeLike<&[u8]> for BytesMut {}
+	impl EncodeLike<Vec<u8>> for BytesMut {}
+	impl EncodeLike<BytesMut> for &[u8] {}
+	impl EncodeLike<BytesMut> for Vec<u8> {}
+}
+
+#[cfg(feature = "bytes")]
+struct BytesCursor {
+	bytes: bytes::Bytes,
+}
+
+#[cfg(feature = "bytes")]
+impl Input for BytesCursor {
+	fn remaining_len(&mut self) -> Result<Option<usize>, Error> {
+		Ok(Some(self.bytes.len()))
+	}
+
+	fn read(&mut self, into: &mut [u8]) -> Result<(), Error> {
+		if into.len() > self.bytes.len() {
+			return Err("Not enough data to fill buffer".into());
+		}
+
+		into.copy_from_slice(&self.bytes[..into.len()]);
+		bytes::Buf::advance(&mut self.bytes, into.len());
+		Ok(())
+	}
+
+	fn scale_internal_decode_bytes(&mut self) -> Result<bytes::Bytes, Error> {
+		let len = <Compact<u32>>::decode(self)?.0 as usize;
+		if len > self.bytes.len() {
+			return Err("Not enough data to fill buffer".into());
+		}
+
+		Ok(self.bytes.split_to(len))
+	}
+}
+
+/// Decodes a given `T` from `Bytes`.
+///
+/// All `bytes::Bytes` decoded from the input share its buffer instead of copying the data.
+#[cfg(feature = "bytes")]
+pub fn decode_from_bytes<T: Decode>(bytes: bytes::Bytes) -> Result<T, Error> {
+	T::decode(&mut BytesCursor { bytes })
+}
+
+#[cfg(feature = "bytes")]
+impl Decode for bytes::Bytes {
+	fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+		input.scale_internal_decode_bytes()
+	}
+}
+
+#[cfg(feature = "bytes")]
+impl Decode for bytes::BytesMut {
+	fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+		Vec::<u8>::decode(input).map(|v| bytes::BytesMut::from(&v[..]))
+	}
 }
 
 impl<T, X> Encode for X where
@@ -398,6 +621,13 @@ pub trait WrapperTypeDecode: Sized {
 impl<T> WrapperTypeDecode for Box<T> {
 	type Wrapped = T;
 }
+impl<T> WrapperTypeDecode for Box<[T]> {
+	type Wrapped = Vec<T>;
+}
+#[cfg(any(feature = "std", feature = "full"))]
+impl WrapperTypeDecode for Box<str> {
+	type Wrapped = String;
+}
 #[cfg(any(feature = "std", feature = "full"))]
 impl<T> WrapperTypeDecode for Arc<T> {
 	type Wrapped = T;
@@ -406,6 +636,22 @@ impl<T> WrapperTypeDecode for Arc<T> {
 impl<T> WrapperTypeDecode for Rc<T> {
 	type Wrapped = T;
 }
+#[cfg(any(feature = "std", feature = "full"))]
+impl<T> WrapperTypeDecode for Arc<[T]> {
+	type Wrapped = Vec<T>;
+}
+#[cfg(any(feature = "std", feature = "full"))]
+impl<T> WrapperTypeDecode for Rc<[T]> {
+	type Wrapped = Vec<T>;
+}
+#[cfg(any(feature = "std", feature = "full"))]
+impl WrapperTypeDecode for Arc<str> {
+	type Wrapped = String;
+}
+#[cfg(any(feature = "std", feature = "full"))]
+impl WrapperTypeDecode for Rc<str> {
+	type Wrapped = String;
+}
 
 impl<T, X> Decode for X where
 	T: Decode + Into<X>,
@@ -496,6 +742,52 @@ impl<T: Decode, E: Decode> Decode for Result<T, E> {
 	}
 }
 
+impl<B: Encode, C: Encode> Encode for ControlFlow<B, C> {
+	fn size_hint(&self) -> usize {
+		1 + match *self {
+			ControlFlow::Continue(ref c) => c.size_hint(),
+			ControlFlow::Break(ref b) => b.size_hint(),
+		}
+	}
+
+	fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+		match *self {
+			ControlFlow::Continue(ref c) => {
+				dest.push_byte(0);
+				c.encode_to(dest);
+			}
+			ControlFlow::Break(ref b) => {
+				dest.push_byte(1);
+				b.encode_to(dest);
+			}
+		}
+	}
+}
+
+impl<B, LikeB, C, LikeC> EncodeLike<ControlFlow<LikeB, LikeC>> for ControlFlow<B, C>
+where
+	B: EncodeLike<LikeB>,
+	LikeB: Encode,
+	C: EncodeLike<LikeC>,
+	LikeC: Encode,
+{}
+
+impl<B: Decode, C: Decode> Decode for ControlFlow<B, C> {
+	fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+		match input.read_byte()
+			.map_err(|e| e.chain("Could not decode variant byte for `ControlFlow`"))?
+		{
+			0 => Ok(ControlFlow::Continue(
+				C::decode(input).map_err(|e| e.chain("Could not decode `ControlFlow::Continue(C)`"))?
+			)),
+			1 => Ok(ControlFlow::Break(
+				B::decode(input).map_err(|e| e.chain("Could not decode `ControlFlow::Break(B)`"))?
+			)),
+			_ => Err("unexpected first byte decoding ControlFlow".into()),
+		}
+	}
+}
+
 /// Shim type because we can't do a specialised implementation for `Option<bool>` directly.
 #[derive(Eq, PartialEq, Clone, Copy)]
 pub struct OptionBool(pub Option<bool>);
@@ -535,6 +827,11 @@ impl Decode for OptionBool {
 
 impl<T: EncodeLike<U>, U: Encode> EncodeLike<Option<U>> for Option<T> {}
 
+/// `None` is encoded as `0` and `Some(t)` as `1` followed by `t`.
+///
+/// Nested options are never flattened: every level of `Option` contributes its own tag byte, so
+/// `Option<Option<T>>` encodes `None` as `00`, `Some(None)` as `01 00` and `Some(Some(t))` as
+/// `01 01` followed by `t`.
 impl<T: Encode> Encode for Option<T> {
 	fn size_hint(&self) -> usize {
 		1 + match *self {
@@ -568,6 +865,21 @@ impl<T: Decode> Decode for Option<T> {
 	}
 }
 
+/// Shim type because we can't do a specialised implementation for `Option<NonZero*>` directly.
+///
+/// Unlike `Option<T>`, this uses the niche of the non-zero type instead of a tag byte: the value is
+/// encoded as the plain integer, with `0` meaning `None`. So `OptionNonZero<NonZeroU32>` is always
+/// 4 bytes, while `Option<NonZeroU32>` is 1 byte for `None` and 5 bytes for `Some`. The two
+/// encodings are not compatible with each other.
+#[derive(Eq, PartialEq, Clone, Copy, Default)]
+pub struct OptionNonZero<T>(pub Option<T>);
+
+impl<T: fmt::Debug> fmt::Debug for OptionNonZero<T> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		self.0.fmt(f)
+	}
+}
+
 macro_rules! impl_for_non_zero {
 	( $( $name:ty ),* $(,)? ) => {
 		$(
@@ -594,6 +906,28 @@ macro_rules! impl_for_non_zero {
 					Self::new(Decode::decode(input)?)
 						.ok_or_else(|| Error::from("cannot create non-zero number from 0"))
 				}
+
+				fn encoded_fixed_size() -> Option<usize> {
+					Some(mem::size_of::<$name>())
+				}
+			}
+
+			impl Encode for OptionNonZero<$name> {
+				fn size_hint(&self) -> usize {
+					mem::size_of::<$name>()
+				}
+
+				fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+					self.0.map_or(0, <$name>::get).encode_to(dest)
+				}
+			}
+
+			impl EncodeLike for OptionNonZero<$name> {}
+
+			impl Decode for OptionNonZero<$name> {
+				fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+					Ok(OptionNonZero(<$name>::new(Decode::decode(input)?)))
+				}
 			}
 		)*
 	}
@@ -682,6 +1016,89 @@ pub(crate) fn decode_vec_with_len<T: Decode, I: Input>(
 	}
 }
 
+/// Decode exactly `len` elements which are not prefixed by their number.
+///
+/// This is for formats conveying the number of elements out of band. Like for `Vec<T>`, the
+/// preallocation is bounded by the remaining length of the input, so a wrong `len` can't cause
+/// a huge allocation.
+///
+/// ```
+/// # use parity_scale_codec::decode_vec_exact;
+/// let mut input = &[1, 0, 2, 0, 3][..];
+/// assert_eq!(decode_vec_exact::<u16, _>(2, &mut input), Ok(vec![1, 2]));
+/// assert_eq!(input, &[3]);
+/// ```
+pub fn decode_vec_exact<T: Decode, I: Input>(len: usize, input: &mut I) -> Result<Vec<T>, Error> {
+	decode_vec_with_len(input, len)
+}
+
+/// Decode a version byte followed by a `Vec<T>`, failing if the version is not `expected`.
+///
+/// This is the decoding side of `(version, records).encode()`, for formats guarding their
+/// records by a schema version. The version is checked before any record is decoded.
+///
+/// ```
+/// # use parity_scale_codec::{Encode, decode_versioned};
+/// let encoded = (2u8, vec![1u16, 2]).encode();
+/// assert_eq!(decode_versioned::<u16, _>(2, &mut &encoded[..]), Ok(vec![1, 2]));
+/// assert!(decode_versioned::<u16, _>(1, &mut &encoded[..]).is_err());
+/// ```
+pub fn decode_versioned<T: Decode, I: Input>(expected: u8, input: &mut I) -> Result<Vec<T>, Error> {
+	let version = input.read_byte().map_err(|e| e.chain("Could not decode the version byte"))?;
+	if version != expected {
+		return Err("Unexpected version byte".into())
+	}
+	Vec::decode(input)
+}
+
+/// Attempt to decode `count` elements which are not prefixed by their number, recording the
+/// result of each of them.
+///
+/// Unlike [`decode_vec_exact`], decoding goes on after a failed element. This is only possible
+/// for a fixed size `T` (see [`Decode::encoded_fixed_size`]): each element is decoded from its own
+/// `encoded_fixed_size` bytes, so the next element starts at a known offset. For other types, the
+/// end of a failed element is unknown, and decoding stops after the first error. It also stops
+/// when the input ends. The returned `Vec` thus has less than `count` results in these cases, the
+/// last one being the error.
+///
+/// ```
+/// # use parity_scale_codec::decode_batch;
+/// let mut input = &[1, 2, 0, 1][..];
+/// let results = decode_batch::<bool, _>(4, &mut input);
+/// assert_eq!(results[0], Ok(true));
+/// assert!(results[1].is_err());
+/// assert_eq!(results[2..], [Ok(false), Ok(true)]);
+/// ```
+pub fn decode_batch<T: Decode, I: Input>(count: usize, input: &mut I) -> Vec<Result<T, Error>> {
+	let capacity = input.remaining_len().ok().flatten().unwrap_or(0).min(count);
+	let mut results = Vec::with_capacity(capacity);
+
+	match T::encoded_fixed_size() {
+		Some(size) => {
+			let mut buffer = vec![0u8; size];
+			for _ in 0..count {
+				if let Err(e) = input.read(&mut buffer) {
+					results.push(Err(e.chain("Could not read the next element")));
+					break;
+				}
+				results.push(T::decode(&mut &buffer[..]));
+			}
+		},
+		None => {
+			for _ in 0..count {
+				let result = T::decode(input);
+				let failed = result.is_err();
+				results.push(result);
+				if failed {
+					break;
+				}
+			}
+		},
+	}
+
+	results
+}
+
 impl_for_non_zero! {
 	NonZeroI8,
 	NonZeroI16,
@@ -695,6 +1112,56 @@ impl_for_non_zero! {
 	NonZeroU128,
 }
 
+/// Implements the codec traits for atomics so that they encode like the plain value they hold.
+///
+/// The value is read with `load(Ordering::Relaxed)`, so encoding gives no synchronization
+/// guarantee with other threads: concurrent stores may or may not be observed.
+macro_rules! impl_for_atomic {
+	( $( $target_has_atomic:literal: $name:ident => $ty:ty ),* $(,)? ) => {
+		$(
+			#[cfg(target_has_atomic = $target_has_atomic)]
+			impl Encode for core::sync::atomic::$name {
+				fn size_hint(&self) -> usize {
+					mem::size_of::<$ty>()
+				}
+
+				fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+					self.load(core::sync::atomic::Ordering::Relaxed).encode_to(dest)
+				}
+
+				fn using_encoded<R, F: FnOnce(&[u8]) -> R>(&self, f: F) -> R {
+					self.load(core::sync::atomic::Ordering::Relaxed).using_encoded(f)
+				}
+			}
+
+			#[cfg(target_has_atomic = $target_has_atomic)]
+			impl EncodeLike for core::sync::atomic::$name {}
+
+			#[cfg(target_has_atomic = $target_has_atomic)]
+			impl EncodeLike<$ty> for core::sync::atomic::$name {}
+
+			#[cfg(target_has_atomic = $target_has_atomic)]
+			impl Decode for core::sync::atomic::$name {
+				fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+					<$ty>::decode(input).map(Self::new)
+				}
+			}
+		)*
+	}
+}
+
+impl_for_atomic! {
+	"8": AtomicBool => bool,
+	"8": AtomicI8 => i8,
+	"16": AtomicI16 => i16,
+	"32": AtomicI32 => i32,
+	"64": AtomicI64 => i64,
+	"8": AtomicU8 => u8,
+	"16": AtomicU16 => u16,
+	"32": AtomicU32 => u32,
+	"64": AtomicU64 => u64,
+}
+
 impl<T: Encode, const N: usize> Encode for [T; N] {
 	fn size_hint(&self) -> usize {
 		mem::size_of::<T>() * N
@@ -707,6 +1174,13 @@ impl<T: Encode, const N: usize> Encode for [T; N] {
 
 impl<T: Decode, const N: usize> Decode for [T; N] {
 	fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+		if matches!(<T as Decode>::TYPE_INFO, TypeInfo::U8 | TypeInfo::I8) {
+			let mut bytes = [0u8; N];
+			input.decode_bytes_into(&mut bytes[..])?;
+			// `T` is `u8` or `i8`, so `[T; N]` has the same layout as `[u8; N]`.
+			return Ok(unsafe { mem::transmute_copy::<[u8; N], [T; N]>(&bytes) })
+		}
+
 		let mut array = ArrayVec::new();
 		for _ in 0..N {
 			array.push(T::decode(input)?);
@@ -717,6 +1191,10 @@ impl<T: Decode, const N: usize> Decode for [T; N] {
 			Err(_) => panic!("We decode `N` elements; qed"),
 		}
 	}
+
+	fn encoded_fixed_size() -> Option<usize> {
+		T::encoded_fixed_size().map(|size| size * N)
+	}
 }
 
 impl<T: EncodeLike<U>, U: Encode, const N: usize> EncodeLike<[U; N]> for [T; N] {}
@@ -739,6 +1217,12 @@ impl Encode for str {
 	}
 }
 
+/// A `Cow` is encoded like the value it borrows or owns, and always decoded as `Cow::Owned`.
+///
+/// `Cow<str>` is decoded like a `String`, validating the UTF-8, and `Cow<[u8]>` like a `Vec<u8>`,
+/// reading the bytes in chunks. To borrow from a byte slice input instead, see [`DecodeCow`].
+///
+/// [`DecodeCow`]: crate::DecodeCow
 impl<'a, T: ToOwned + ?Sized> Decode for Cow<'a, T>
 	where <T as ToOwned>::Owned: Decode,
 {
@@ -759,57 +1243,254 @@ impl<T> Decode for PhantomData<T> {
 	}
 }
 
-#[cfg(any(feature = "std", feature = "full"))]
-impl Decode for String {
+impl<T: Encode> EncodeLike for Wrapping<T> {}
+
+impl<T: Encode> Encode for Wrapping<T> {
+	fn size_hint(&self) -> usize {
+		self.0.size_hint()
+	}
+
+	fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+		self.0.encode_to(dest)
+	}
+
+	fn using_encoded<R, F: FnOnce(&[u8]) -> R>(&self, f: F) -> R {
+		self.0.using_encoded(f)
+	}
+}
+
+impl<T: Decode> Decode for Wrapping<T> {
 	fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
-		Self::from_utf8(Vec::decode(input)?).map_err(|_| "Invalid utf8 sequence".into())
+		T::decode(input).map(Wrapping)
 	}
 }
 
-/// Writes the compact encoding of `len` do `dest`.
-pub(crate) fn compact_encode_len_to<W: Output + ?Sized>(dest: &mut W, len: usize) -> Result<(), Error> {
-	if len > u32::max_value() as usize {
-		return Err("Attempted to serialize a collection with too many elements.".into());
+impl EncodeLike for PhantomPinned {}
+
+impl Encode for PhantomPinned {
+	fn encode_to<W: Output + ?Sized>(&self, _dest: &mut W) {}
+}
+
+impl Decode for PhantomPinned {
+	fn decode<I: Input>(_input: &mut I) -> Result<Self, Error> {
+		Ok(PhantomPinned)
 	}
+}
 
-	Compact(len as u32).encode_to(dest);
-	Ok(())
+#[cfg(any(feature = "std", feature = "full"))]
+impl Decode for String {
+	fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+		Self::from_utf8(Vec::decode(input)?).map_err(|_| "Invalid utf8 sequence".into())
+	}
 }
 
-impl<T: Encode> Encode for [T] {
+/// `CString` is encoded like a `Vec<u8>` of its bytes, the nul terminator is not stored.
+#[cfg(feature = "std")]
+impl Encode for std::ffi::CString {
 	fn size_hint(&self) -> usize {
-		mem::size_of::<u32>() + mem::size_of::<T>() * self.len()
+		self.as_bytes().size_hint()
 	}
 
 	fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
-		compact_encode_len_to(dest, self.len()).expect("Compact encodes length");
+		self.as_bytes().encode_to(dest)
+	}
+}
 
-		encode_slice_no_len(self, dest)
+#[cfg(feature = "std")]
+impl EncodeLike for std::ffi::CString {}
+
+#[cfg(feature = "std")]
+impl Decode for std::ffi::CString {
+	fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+		Self::new(Vec::<u8>::decode(input)?).map_err(|_| "Interior nul byte in `CString`".into())
 	}
 }
 
-/// Create a `Vec<T>` by casting directly from a buffer of read `u8`s
+/// A `Mutex` is encoded like the value it holds, which is locked for the duration of the encoding.
 ///
-/// The encoding of `T` must be equal to its binary representation, and size of `T` must be less or
-/// equal to [`MAX_PREALLOCATION`].
-pub(crate) fn read_vec_from_u8s<I, T>(input: &mut I, items_len: usize) -> Result<Vec<T>, Error>
-where
-	I: Input,
-	T: ToMutByteSlice + Default + Clone,
-{
-	debug_assert!(MAX_PREALLOCATION >= mem::size_of::<T>(), "Invalid precondition");
+/// `Encode` can not fail, so the value of a poisoned `Mutex` is encoded as is rather than
+/// panicking. Use [`try_encode_mutex`] to get an error instead. Encoding blocks while the lock is
+/// held elsewhere and deadlocks if it is held by the current thread.
+#[cfg(feature = "std")]
+impl<T: Encode> Encode for std::sync::Mutex<T> {
+	fn size_hint(&self) -> usize {
+		self.lock().unwrap_or_else(std::sync::PoisonError::into_inner).size_hint()
+	}
 
-	let byte_len = items_len.checked_mul(mem::size_of::<T>())
-		.ok_or_else(|| "Item is too big and cannot be allocated")?;
+	fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+		self.lock().unwrap_or_else(std::sync::PoisonError::into_inner).encode_to(dest)
+	}
+}
 
-	let input_len = input.remaining_len()?;
+#[cfg(feature = "std")]
+impl<T: Encode> EncodeLike for std::sync::Mutex<T> {}
 
-	// If there is input len and it cannot be pre-allocated then return directly.
-	if input_len.map(|l| l < byte_len).unwrap_or(false) {
-		return Err("Not enough data to decode vector".into())
+#[cfg(feature = "std")]
+impl<T: Decode> Decode for std::sync::Mutex<T> {
+	fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+		T::decode(input).map(Self::new)
 	}
+}
 
-	// In both these branches we're going to be creating and resizing a Vec<T>,
+/// A `RwLock` is encoded like the value it holds, which is read locked for the duration of the
+/// encoding.
+///
+/// Like for `Mutex`, the value of a poisoned `RwLock` is encoded as is rather than panicking. Use
+/// [`try_encode_rw_lock`] to get an error instead.
+#[cfg(feature = "std")]
+impl<T: Encode> Encode for std::sync::RwLock<T> {
+	fn size_hint(&self) -> usize {
+		self.read().unwrap_or_else(std::sync::PoisonError::into_inner).size_hint()
+	}
+
+	fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+		self.read().unwrap_or_else(std::sync::PoisonError::into_inner).encode_to(dest)
+	}
+}
+
+#[cfg(feature = "std")]
+impl<T: Encode> EncodeLike for std::sync::RwLock<T> {}
+
+#[cfg(feature = "std")]
+impl<T: Decode> Decode for std::sync::RwLock<T> {
+	fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+		T::decode(input).map(Self::new)
+	}
+}
+
+/// Encode the value held by `mutex` like [`Encode::encode`], returning an error instead of
+/// encoding the value if the `Mutex` is poisoned.
+///
+/// ```
+/// # use parity_scale_codec::{Encode, try_encode_mutex};
+/// let mutex = std::sync::Mutex::new(5u32);
+/// assert_eq!(try_encode_mutex(&mutex), Ok(5u32.encode()));
+/// ```
+#[cfg(feature = "std")]
+pub fn try_encode_mutex<T: Encode>(mutex: &std::sync::Mutex<T>) -> Result<Vec<u8>, Error> {
+	mutex.lock()
+		.map(|value| value.encode())
+		.map_err(|_| "Attempted to encode a poisoned `Mutex`".into())
+}
+
+/// Encode the value held by `rw_lock` like [`Encode::encode`], returning an error instead of
+/// encoding the value if the `RwLock` is poisoned.
+///
+/// ```
+/// # use parity_scale_codec::{Encode, try_encode_rw_lock};
+/// let rw_lock = std::sync::RwLock::new(5u32);
+/// assert_eq!(try_encode_rw_lock(&rw_lock), Ok(5u32.encode()));
+/// ```
+#[cfg(feature = "std")]
+pub fn try_encode_rw_lock<T: Encode>(rw_lock: &std::sync::RwLock<T>) -> Result<Vec<u8>, Error> {
+	rw_lock.read()
+		.map(|value| value.encode())
+		.map_err(|_| "Attempted to encode a poisoned `RwLock`".into())
+}
+
+/// Writes the compact encoding of `len` do `dest`.
+pub(crate) fn compact_encode_len_to<W: Output + ?Sized>(dest: &mut W, len: usize) -> Result<(), Error> {
+	if len > u32::max_value() as usize {
+		return Err("Attempted to serialize a collection with too many elements.".into());
+	}
+
+	Compact(len as u32).encode_to(dest);
+	Ok(())
+}
+
+/// Encode the items of `iter` like a `Vec` of them, without collecting them first.
+///
+/// The length prefix is taken from [`ExactSizeIterator::len`], which must thus be exact: the
+/// output is not decodable if the iterator yields another number of items.
+///
+/// ```
+/// # use parity_scale_codec::{Encode, encode_iter};
+/// let mut encoded = Vec::new();
+/// encode_iter((1..4u16).map(|i| i * 2), &mut encoded);
+/// assert_eq!(encoded, vec![2u16, 4, 6].encode());
+/// ```
+pub fn encode_iter<I, W>(iter: I, dest: &mut W) where
+	I: ExactSizeIterator,
+	I::Item: Encode,
+	W: Output + ?Sized,
+{
+	compact_encode_len_to(dest, iter.len()).expect("Compact encodes length");
+
+	for item in iter {
+		item.encode_to(dest);
+	}
+}
+
+/// Encode `items` like a `Vec` of them, calling `yield_now` after every `every` items.
+///
+/// This lets a cooperative scheduler run other tasks during the encoding of a very large
+/// collection. `yield_now` is not called after the last items if there are less than `every`
+/// of them. Unlike encoding the slice directly, the items are always encoded one by one.
+///
+/// # Panics
+///
+/// If `every` is zero.
+///
+/// ```
+/// # use parity_scale_codec::{Encode, encode_to_yielding};
+/// let mut yields = 0;
+/// let mut encoded = Vec::new();
+/// encode_to_yielding(&[1u32, 2, 3, 4, 5], &mut encoded, 2, || yields += 1);
+/// assert_eq!(encoded, vec![1u32, 2, 3, 4, 5].encode());
+/// assert_eq!(yields, 2);
+/// ```
+pub fn encode_to_yielding<T, W, F>(items: &[T], dest: &mut W, every: usize, mut yield_now: F) where
+	T: Encode,
+	W: Output + ?Sized,
+	F: FnMut(),
+{
+	assert!(every > 0, "Items between yields must be more than zero");
+	compact_encode_len_to(dest, items.len()).expect("Compact encodes length");
+
+	for chunk in items.chunks(every) {
+		for item in chunk {
+			item.encode_to(dest);
+		}
+		if chunk.len() == every {
+			yield_now();
+		}
+	}
+}
+
+impl<T: Encode> Encode for [T] {
+	fn size_hint(&self) -> usize {
+		mem::size_of::<u32>() + mem::size_of::<T>() * self.len()
+	}
+
+	fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+		compact_encode_len_to(dest, self.len()).expect("Compact encodes length");
+
+		encode_slice_no_len(self, dest)
+	}
+}
+
+/// Create a `Vec<T>` by casting directly from a buffer of read `u8`s
+///
+/// The encoding of `T` must be equal to its binary representation, and size of `T` must be less or
+/// equal to [`MAX_PREALLOCATION`].
+pub(crate) fn read_vec_from_u8s<I, T>(input: &mut I, items_len: usize) -> Result<Vec<T>, Error>
+where
+	I: Input,
+	T: ToMutByteSlice + Default + Clone,
+{
+	debug_assert!(MAX_PREALLOCATION >= mem::size_of::<T>(), "Invalid precondition");
+
+	let byte_len = items_len.checked_mul(mem::size_of::<T>())
+		.ok_or_else(|| "Item is too big and cannot be allocated")?;
+
+	let input_len = input.remaining_len()?;
+
+	// If there is input len and it cannot be pre-allocated then return directly.
+	if input_len.map(|l| l < byte_len).unwrap_or(false) {
+		return Err("Not enough data to decode vector".into())
+	}
+
+	// In both these branches we're going to be creating and resizing a Vec<T>,
 	// but casting it to a &mut [u8] for reading.
 
 	// Note: we checked that if input_len is some then it can preallocated.
@@ -858,6 +1539,14 @@ impl<T> WrapperTypeEncode for Vec<T> {}
 impl<T: EncodeLike<U>, U: Encode> EncodeLike<Vec<U>> for Vec<T> {}
 impl<T: EncodeLike<U>, U: Encode> EncodeLike<&[U]> for Vec<T> {}
 impl<T: EncodeLike<U>, U: Encode> EncodeLike<Vec<U>> for &[T] {}
+impl<T: EncodeLike<U>, U: Encode> EncodeLike<Box<[U]>> for &[T] {}
+impl<T: EncodeLike<U>, U: Encode> EncodeLike<Box<[U]>> for Vec<T> {}
+impl<T: EncodeLike<U>, U: Encode> EncodeLike<&[U]> for Box<[T]> {}
+impl<T: EncodeLike<U>, U: Encode> EncodeLike<Vec<U>> for Box<[T]> {}
+impl<T: EncodeLike<U> + Clone, U: Encode> EncodeLike<Vec<U>> for Cow<'_, [T]> {}
+impl<T: EncodeLike<U> + Clone, U: Encode> EncodeLike<&[U]> for Cow<'_, [T]> {}
+impl<T: EncodeLike<U>, U: Encode + Clone> EncodeLike<Cow<'_, [U]>> for Vec<T> {}
+impl<T: EncodeLike<U>, U: Encode + Clone> EncodeLike<Cow<'_, [U]>> for &[T] {}
 
 impl<T: Decode> Decode for Vec<T> {
 	fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
@@ -913,14 +1602,50 @@ macro_rules! impl_codec_through_iterator {
 impl_codec_through_iterator! {
 	BTreeMap { K: Ord, V } { LikeK, LikeV}
 		{ K: EncodeLike<LikeK>, LikeK: Encode, V: EncodeLike<LikeV>, LikeV: Encode }
-	BTreeSet { T: Ord } { LikeT }
-		{ T: EncodeLike<LikeT>, LikeT: Encode }
 	LinkedList { T } { LikeT }
 		{ T: EncodeLike<LikeT>, LikeT: Encode }
 	BinaryHeap { T: Ord } { LikeT }
 		{ T: EncodeLike<LikeT>, LikeT: Encode }
 }
 
+impl<T: Encode> Encode for BTreeSet<T> {
+	fn size_hint(&self) -> usize {
+		mem::size_of::<u32>() + mem::size_of::<T>() * self.len()
+	}
+
+	fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+		compact_encode_len_to(dest, self.len()).expect("Compact encodes length");
+
+		for i in self.iter() {
+			i.encode_to(dest);
+		}
+	}
+}
+
+/// A `BTreeSet` is encoded in ascending order, so its decoding rejects any other order, as well as
+/// duplicated elements. This ensures that a set has a single valid encoding.
+impl<T: Decode + Ord> Decode for BTreeSet<T> {
+	fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+		<Compact<u32>>::decode(input).and_then(move |Compact(len)| {
+			input.descend_ref()?;
+			let mut set = BTreeSet::new();
+			for _ in 0..len {
+				let element = T::decode(input)?;
+				if matches!(set.last(), Some(last) if *last >= element) {
+					return Err("Non-canonical `BTreeSet`: elements are not strictly ascending".into());
+				}
+				set.insert(element);
+			}
+			input.ascend_ref();
+			Ok(set)
+		})
+	}
+}
+
+impl<T: EncodeLike<LikeT>, LikeT: Encode> EncodeLike<BTreeSet<LikeT>> for BTreeSet<T> {}
+impl<T: EncodeLike<LikeT>, LikeT: Encode> EncodeLike<&[(LikeT,)]> for BTreeSet<T> {}
+impl<T: EncodeLike<LikeT>, LikeT: Encode> EncodeLike<BTreeSet<LikeT>> for &[(T,)] {}
+
 impl<T: Encode> EncodeLike for VecDeque<T> {}
 impl<T: EncodeLike<U>, U: Encode> EncodeLike<&[U]> for VecDeque<T> {}
 impl<T: EncodeLike<U>, U: Encode> EncodeLike<VecDeque<U>> for &[T] {}
@@ -992,6 +1717,20 @@ impl Decode for () {
 	}
 }
 
+impl EncodeLike for Infallible {}
+
+impl Encode for Infallible {
+	fn encode_to<W: Output + ?Sized>(&self, _dest: &mut W) {
+		match *self {}
+	}
+}
+
+impl Decode for Infallible {
+	fn decode<I: Input>(_: &mut I) -> Result<Self, Error> {
+		Err("`Infallible` has no values and can not be decoded".into())
+	}
+}
+
 macro_rules! impl_len {
 	( $( $type:ident< $($g:ident),* > ),* ) => { $(
 		impl<$($g),*> DecodeLength for $type<$($g),*> {
@@ -1006,6 +1745,100 @@ macro_rules! impl_len {
 // Collection types that support compact decode length.
 impl_len!(Vec<T>, BTreeSet<T>, BTreeMap<K, V>, VecDeque<T>, BinaryHeap<T>, LinkedList<T>);
 
+/// The iteration order of a `HashSet` is arbitrary, so its elements are sorted by their encoding
+/// to get the same output for equal sets. Decoding rejects duplicated elements.
+#[cfg(feature = "std")]
+impl<T: Encode, S> Encode for std::collections::HashSet<T, S> {
+	fn size_hint(&self) -> usize {
+		mem::size_of::<u32>() + self.iter().map(Encode::size_hint).sum::<usize>()
+	}
+
+	fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+		compact_encode_len_to(dest, self.len()).expect("Compact encodes length");
+
+		let mut elements: Vec<Vec<u8>> = self.iter().map(Encode::encode).collect();
+		elements.sort_unstable();
+		for element in &elements {
+			dest.write(element);
+		}
+	}
+}
+
+#[cfg(feature = "std")]
+impl<T: Encode, S> EncodeLike for std::collections::HashSet<T, S> {}
+
+#[cfg(feature = "std")]
+impl<T, S> Decode for std::collections::HashSet<T, S> where
+	T: Decode + Eq + core::hash::Hash,
+	S: core::hash::BuildHasher + Default,
+{
+	fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+		<Compact<u32>>::decode(input).and_then(move |Compact(len)| {
+			input.descend_ref()?;
+			let mut set = Self::with_hasher(S::default());
+			for _ in 0..len {
+				if !set.insert(T::decode(input)?) {
+					return Err("Duplicate element in `HashSet`".into());
+				}
+			}
+			input.ascend_ref();
+			Ok(set)
+		})
+	}
+}
+
+/// Like for `HashSet`, the entries of a `HashMap` are sorted by the encoding of their key to get
+/// the same output for equal maps. Decoding rejects duplicated keys. Any hasher implementing
+/// `Default` can be used, the encoding doesn't depend on it.
+///
+/// The entries are ordered by comparing the encoded keys byte by byte, a key being a prefix of
+/// another one coming first. This is not the `Ord` of the keys: `-1i32` is encoded as
+/// `[0xff; 4]` and comes after `1i32`, and `256u16` comes before `1u16` as the integers are little
+/// endian. Distinct keys can't have the same encoding in valid SCALE, should it happen anyway the
+/// entries are ordered by the encoding of their value.
+#[cfg(feature = "std")]
+impl<K: Encode, V: Encode, S> Encode for std::collections::HashMap<K, V, S> {
+	fn size_hint(&self) -> usize {
+		mem::size_of::<u32>() + self.iter().map(|(k, v)| k.size_hint() + v.size_hint()).sum::<usize>()
+	}
+
+	fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+		compact_encode_len_to(dest, self.len()).expect("Compact encodes length");
+
+		let mut entries: Vec<(Vec<u8>, &V)> = self.iter().map(|(k, v)| (k.encode(), v)).collect();
+		entries.sort_unstable_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.encode().cmp(&b.1.encode())));
+		for (key, value) in &entries {
+			dest.write(key);
+			value.encode_to(dest);
+		}
+	}
+}
+
+#[cfg(feature = "std")]
+impl<K: Encode, V: Encode, S> EncodeLike for std::collections::HashMap<K, V, S> {}
+
+#[cfg(feature = "std")]
+impl<K, V, S> Decode for std::collections::HashMap<K, V, S> where
+	K: Decode + Eq + core::hash::Hash,
+	V: Decode,
+	S: core::hash::BuildHasher + Default,
+{
+	fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+		<Compact<u32>>::decode(input).and_then(move |Compact(len)| {
+			input.descend_ref()?;
+			let mut map = Self::with_hasher(S::default());
+			for _ in 0..len {
+				let (key, value) = <(K, V)>::decode(input)?;
+				if map.insert(key, value).is_some() {
+					return Err("Duplicate key in `HashMap`".into());
+				}
+			}
+			input.ascend_ref();
+			Ok(map)
+		})
+	}
+}
+
 macro_rules! tuple_impl {
 	(
 		($one:ident, $extra:ident),
@@ -1131,6 +1964,10 @@ macro_rules! impl_endians {
 				input.read(&mut buf)?;
 				Ok(<$t>::from_le_bytes(buf))
 			}
+
+			fn encoded_fixed_size() -> Option<usize> {
+				Some(mem::size_of::<$t>())
+			}
 		}
 	)* }
 }
@@ -1156,6 +1993,10 @@ macro_rules! impl_one_byte {
 			fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
 				Ok(input.read_byte()? as $t)
 			}
+
+			fn encoded_fixed_size() -> Option<usize> {
+				Some(1)
+			}
 		}
 	)* }
 }
@@ -1184,6 +2025,10 @@ impl Decode for bool {
 			_ => Err("Invalid boolean representation".into())
 		}
 	}
+
+	fn encoded_fixed_size() -> Option<usize> {
+		Some(1)
+	}
 }
 
 impl Encode for Duration {
@@ -1260,6 +2105,75 @@ where
 	}
 }
 
+impl<T: Encode> EncodeLike for RangeFrom<T> {}
+
+impl<T: Encode> Encode for RangeFrom<T> {
+	fn size_hint(&self) -> usize {
+		self.start.size_hint()
+	}
+
+	fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+		self.start.encode_to(dest)
+	}
+}
+
+impl<T: Decode> Decode for RangeFrom<T> {
+	fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+		let start = T::decode(input).map_err(|e| e.chain("Could not decode `RangeFrom<T>`"))?;
+		Ok(RangeFrom { start })
+	}
+}
+
+impl<T: Encode> EncodeLike for RangeTo<T> {}
+
+impl<T: Encode> Encode for RangeTo<T> {
+	fn size_hint(&self) -> usize {
+		self.end.size_hint()
+	}
+
+	fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+		self.end.encode_to(dest)
+	}
+}
+
+impl<T: Decode> Decode for RangeTo<T> {
+	fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+		let end = T::decode(input).map_err(|e| e.chain("Could not decode `RangeTo<T>`"))?;
+		Ok(RangeTo { end })
+	}
+}
+
+impl<T: Encode> EncodeLike for RangeToInclusive<T> {}
+
+impl<T: Encode> Encode for RangeToInclusive<T> {
+	fn size_hint(&self) -> usize {
+		self.end.size_hint()
+	}
+
+	fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+		self.end.encode_to(dest)
+	}
+}
+
+impl<T: Decode> Decode for RangeToInclusive<T> {
+	fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+		let end = T::decode(input).map_err(|e| e.chain("Could not decode `RangeToInclusive<T>`"))?;
+		Ok(..=end)
+	}
+}
+
+impl EncodeLike for RangeFull {}
+
+impl Encode for RangeFull {
+	fn encode_to<W: Output + ?Sized>(&self, _dest: &mut W) {}
+}
+
+impl Decode for RangeFull {
+	fn decode<I: Input>(_input: &mut I) -> Result<Self, Error> {
+		Ok(RangeFull)
+	}
+}
+
 
 #[cfg(test)]
 mod tests {
@@ -1304,6 +2218,36 @@ mod tests {
 		assert_eq!(*z, *x);
 	}
 
+	#[test]
+	fn cow_str_and_bytes_round_trip() {
+		fn assert_encode_like<T: Encode, R: EncodeLike<T>>(value: R, expected: &T) {
+			assert_eq!(value.encode(), expected.encode());
+		}
+
+		let borrowed: Cow<'_, str> = Cow::Borrowed("hello");
+		let owned: Cow<'_, str> = Cow::Owned("hello".into());
+		assert_eq!(borrowed.encode(), "hello".encode());
+		assert_eq!(owned.encode(), "hello".encode());
+		assert_eq!(borrowed.size_hint(), "hello".size_hint());
+		assert!(matches!(<Cow<'_, str>>::decode(&mut &borrowed.encode()[..]), Ok(Cow::Owned(s)) if s == "hello"));
+		assert_encode_like(borrowed.clone(), &String::from("hello"));
+		assert_encode_like(owned, &"hello");
+		assert_encode_like(String::from("hello"), &borrowed);
+		assert!(<Cow<'_, str>>::decode(&mut &vec![0xffu8, 0xfe].encode()[..]).is_err());
+
+		let borrowed: Cow<'_, [u8]> = Cow::Borrowed(&[1, 2, 3]);
+		let owned: Cow<'_, [u8]> = Cow::Owned(vec![1, 2, 3]);
+		assert_eq!(borrowed.encode(), vec![1u8, 2, 3].encode());
+		assert_eq!(owned.encode(), vec![1u8, 2, 3].encode());
+		assert!(matches!(<Cow<'_, [u8]>>::decode(&mut &owned.encode()[..]), Ok(Cow::Owned(v)) if v == [1, 2, 3]));
+		assert_encode_like(borrowed.clone(), &vec![1u8, 2, 3]);
+		assert_encode_like(owned, &&[1u8, 2, 3][..]);
+		assert_encode_like(vec![1u8, 2, 3], &borrowed);
+
+		// A length larger than the input is not trusted.
+		assert!(<Cow<'_, [u8]>>::decode(&mut &Compact(u32::max_value()).encode()[..]).is_err());
+	}
+
 	fn hexify(bytes: &[u8]) -> String {
 		bytes.iter().map(|ref b| format!("{:02x}", b)).collect::<Vec<String>>().join(" ")
 	}
@@ -1348,6 +2292,184 @@ mod tests {
 		assert_eq!(<Vec<OptionBool>>::decode(&mut &encoded[..]).unwrap(), value);
 	}
 
+	#[test]
+	fn nested_option_is_not_flattened() {
+		let none: Option<Option<u32>> = None;
+		let some_none: Option<Option<u32>> = Some(None);
+		let some_some: Option<Option<u32>> = Some(Some(5));
+
+		assert_eq!(hexify(&none.encode()), "00");
+		assert_eq!(hexify(&some_none.encode()), "01 00");
+		assert_eq!(hexify(&some_some.encode()), "01 01 05 00 00 00");
+
+		for value in [none, some_none, some_some] {
+			let encoded = value.encode();
+			assert_eq!(encoded.len(), value.size_hint());
+			assert_eq!(<Option<Option<u32>>>::decode(&mut &encoded[..]).unwrap(), value);
+		}
+
+		assert!(<Option<Option<u32>>>::decode(&mut &[1, 2][..]).is_err());
+	}
+
+	#[test]
+	fn unit_payloads_take_no_bytes() {
+		let ok: Result<(), u16> = Ok(());
+		assert_eq!(ok.encode(), vec![0]);
+		assert_eq!(ok.size_hint(), 1);
+		assert_eq!(<Result<(), u16>>::decode(&mut &[0][..]), Ok(ok));
+
+		let err: Result<(), u16> = Err(0x0102);
+		assert_eq!(err.encode(), vec![1, 2, 1]);
+		assert_eq!(<Result<(), u16>>::decode(&mut &[1, 2, 1][..]), Ok(err));
+
+		let err: Result<(), String> = Err("failed".into());
+		assert_eq!(err.encode(), [&[1][..], &"failed".encode()[..]].concat());
+		assert_eq!(<Result<(), String>>::decode(&mut &err.encode()[..]), Ok(err));
+
+		assert_eq!(Some(()).encode(), vec![1]);
+		assert_eq!(None::<()>.encode(), vec![0]);
+		assert_eq!(<Option<()>>::decode(&mut &[1][..]), Ok(Some(())));
+		assert_eq!(<Option<()>>::decode(&mut &[0][..]), Ok(None));
+		assert!(<Option<()>>::decode(&mut &[2][..]).is_err());
+
+		let input = &mut &[0, 7][..];
+		assert_eq!(<Result<(), u8>>::decode(input), Ok(Ok(())));
+		assert_eq!(*input, &[7]);
+	}
+
+	#[test]
+	fn decode_batch_skips_invalid_fixed_size_elements() {
+		let encoded = [
+			&0x0102u16.encode()[..],
+			&[0xff, 0xff][..],
+			&0x0304u16.encode()[..],
+		].concat();
+
+		// `NonZeroU16` can't be zero, an element of zero bytes is invalid.
+		let mut invalid = encoded.clone();
+		invalid.splice(2..4, [0, 0].iter().copied());
+		invalid.push(7);
+		let mut input = &invalid[..];
+		let results = decode_batch::<NonZeroU16, _>(3, &mut input);
+		assert_eq!(results.len(), 3);
+		assert_eq!(results[0], Ok(NonZeroU16::new(0x0102).unwrap()));
+		assert!(results[1].is_err());
+		assert_eq!(results[2], Ok(NonZeroU16::new(0x0304).unwrap()));
+		assert_eq!(input, &[7]);
+
+		let results = decode_batch::<[u8; 2], _>(3, &mut &encoded[..]);
+		assert_eq!(results, vec![Ok([2, 1]), Ok([0xff, 0xff]), Ok([4, 3])]);
+
+		// The input ends in the middle of the third element.
+		let results = decode_batch::<NonZeroU16, _>(4, &mut &invalid[..5]);
+		assert_eq!(results.len(), 3);
+		assert!(results[2].is_err());
+	}
+
+	#[test]
+	fn decode_batch_stops_at_the_first_invalid_variable_size_element() {
+		let encoded = [&Some(1u8).encode()[..], &[2, 1][..], &Some(3u8).encode()[..]].concat();
+		let results = decode_batch::<Option<u8>, _>(3, &mut &encoded[..]);
+		assert_eq!(results.len(), 2);
+		assert_eq!(results[0], Ok(Some(1)));
+		assert!(results[1].is_err());
+	}
+
+	#[test]
+	fn vec_u8_is_read_in_bulk() {
+		// Records the length of each read.
+		struct Reads<'a> {
+			input: &'a [u8],
+			reads: Vec<usize>,
+			known_len: bool,
+		}
+
+		impl<'a> Input for Reads<'a> {
+			fn remaining_len(&mut self) -> Result<Option<usize>, Error> {
+				Ok(if self.known_len { Some(self.input.len()) } else { None })
+			}
+
+			fn read(&mut self, into: &mut [u8]) -> Result<(), Error> {
+				self.reads.push(into.len());
+				self.input.read(into)
+			}
+		}
+
+		let data: Vec<u8> = (0..1u32 << 20).map(|i| (i % 251) as u8).collect();
+		let encoded = data.encode();
+		let prefix_len = encoded.len() - data.len();
+
+		// The length prefix, then all the bytes at once.
+		let mut input = Reads { input: &encoded, reads: Vec::new(), known_len: true };
+		assert_eq!(<Vec<u8>>::decode(&mut input).as_ref(), Ok(&data));
+		assert_eq!(input.reads.iter().sum::<usize>(), encoded.len());
+		assert_eq!(input.reads.last(), Some(&data.len()));
+		assert!(input.reads.len() <= prefix_len + 1);
+
+		// Without known length, the bytes are read in chunks bounding the preallocation.
+		let mut input = Reads { input: &encoded, reads: Vec::new(), known_len: false };
+		assert_eq!(<Vec<u8>>::decode(&mut input).as_ref(), Ok(&data));
+		assert!(input.reads.iter().all(|len| *len <= MAX_PREALLOCATION));
+		assert!(input.reads.len() <= prefix_len + data.len() / MAX_PREALLOCATION);
+	}
+
+	#[test]
+	fn write_byte_repeated_writes_count_bytes() {
+		// Records the writes, to check the chunking of the default implementation.
+		struct Writes(Vec<Vec<u8>>);
+
+		impl Output for Writes {
+			fn write(&mut self, bytes: &[u8]) {
+				self.0.push(bytes.to_vec());
+			}
+		}
+
+		let mut writes = Writes(Vec::new());
+		writes.write_byte_repeated(0xaa, 150);
+		writes.write_byte_repeated(0xbb, 0);
+		assert_eq!(writes.0.iter().map(Vec::len).collect::<Vec<_>>(), vec![64, 64, 22]);
+		assert!(writes.0.concat().iter().all(|b| *b == 0xaa));
+
+		let mut dest = vec![1u8];
+		dest.write_byte_repeated(0, 1000);
+		assert_eq!(dest.len(), 1001);
+		assert_eq!(dest[0], 1);
+		assert!(dest[1..].iter().all(|b| *b == 0));
+
+		// Any `std::io::Write` goes through the blanket implementation.
+		let mut buffer = [1u8; 100];
+		let mut cursor = std::io::Cursor::new(&mut buffer[..]);
+		cursor.set_position(10);
+		cursor.write_byte_repeated(0xcc, 80);
+		assert_eq!(cursor.position(), 90);
+		assert!(buffer[..10].iter().chain(&buffer[90..]).all(|b| *b == 1));
+		assert!(buffer[10..90].iter().all(|b| *b == 0xcc));
+
+		let mut tracker = SizeTracker { written: 0 };
+		tracker.write_byte_repeated(7, 1 << 20);
+		assert_eq!(tracker.written, 1 << 20);
+	}
+
+	#[test]
+	fn option_non_zero_uses_niche() {
+		let none = OptionNonZero::<NonZeroU32>(None);
+		let one = OptionNonZero(NonZeroU32::new(1));
+
+		assert_eq!(hexify(&none.encode()), "00 00 00 00");
+		assert_eq!(hexify(&one.encode()), "01 00 00 00");
+		assert_eq!(one.encode(), 1u32.encode());
+		assert_eq!(none.size_hint(), 4);
+
+		for value in [none, one, OptionNonZero(NonZeroU32::new(u32::max_value()))] {
+			let encoded = value.encode();
+			assert_eq!(OptionNonZero::<NonZeroU32>::decode(&mut &encoded[..]), Ok(value));
+		}
+
+		let value = OptionNonZero(NonZeroI8::new(-1));
+		assert_eq!(OptionNonZero::<NonZeroI8>::decode(&mut &value.encode()[..]), Ok(value));
+		assert!(OptionNonZero::<NonZeroU16>::decode(&mut &[1][..]).is_err());
+	}
+
 	fn test_encode_length<T: Encode + Decode + DecodeLength>(thing: &T, len: usize) {
 		assert_eq!(<T as DecodeLength>::len(&mut &thing.encode()[..]).unwrap(), len);
 	}
@@ -1475,6 +2597,24 @@ mod tests {
 		assert_eq!(Decode::decode(&mut &t8.encode()[..]), Ok(t8));
 	}
 
+	#[test]
+	fn linked_list_encodes_like_vec_in_iteration_order() {
+		let mut list = LinkedList::new();
+		list.push_back(2u16);
+		list.push_front(1);
+		list.push_back(3);
+
+		let encoded = list.encode();
+		assert_eq!(encoded, vec![1u16, 2, 3].encode());
+
+		let decoded = LinkedList::<u16>::decode(&mut &encoded[..]).unwrap();
+		assert_eq!(decoded.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+		assert_eq!(decoded, list);
+
+		assert_eq!(LinkedList::<u16>::decode(&mut &[0][..]), Ok(LinkedList::new()));
+		assert!(LinkedList::<u16>::decode(&mut &[8, 1, 0][..]).is_err());
+	}
+
 	#[test]
 	fn io_reader() {
 		let mut io_reader = IoReader(std::io::Cursor::new(&[1u8, 2, 3][..]));
@@ -1494,6 +2634,21 @@ mod tests {
 		std::rc::Rc::new(10u32).encode();
 	}
 
+	#[test]
+	fn mutable_references_encode_like_the_value() {
+		fn encode_generic<T: Encode + EncodeLike<u64>>(value: T) -> Vec<u8> {
+			value.encode()
+		}
+
+		let mut x = 0x0102_0304u64;
+		let mut s = String::from("scale");
+		assert_eq!((&mut x).encode(), x.encode());
+		assert_eq!((&mut s).encode(), s.encode());
+		assert_eq!((&mut &mut x).encode(), x.encode());
+		assert_eq!(encode_generic(&mut x), x.encode());
+		assert_eq!((&mut x).size_hint(), x.size_hint());
+	}
+
 	#[test]
 	fn not_limit_input_test() {
 		use crate::Input;
@@ -1684,6 +2839,511 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn encode_to_slice_exact_fit() {
+		let value = (7u32, vec![1u8, 2, 3], Compact(300u32));
+		let expected = value.encode();
+		let mut buf = vec![0u8; expected.len()];
+
+		assert_eq!(value.encode_to_slice(&mut buf), Ok(expected.len()));
+		assert_eq!(buf, expected);
+
+		let mut larger = [0xffu8; 32];
+		assert_eq!(value.encode_to_slice(&mut larger), Ok(expected.len()));
+		assert_eq!(&larger[..expected.len()], &expected[..]);
+		assert!(larger[expected.len()..].iter().all(|b| *b == 0xff));
+	}
+
+	#[test]
+	fn encode_to_slice_too_small() {
+		let value = (7u32, vec![1u8, 2, 3]);
+		let mut buf = vec![0u8; value.encoded_size() - 1];
+
+		assert!(value.encode_to_slice(&mut buf).is_err());
+		assert!(1u64.encode_to_slice(&mut []).is_err());
+		assert_eq!(().encode_to_slice(&mut []), Ok(0));
+	}
+
+	#[test]
+	fn tuple_with_compact_prefix_works() {
+		// Values at each compact mode transition.
+		let compacts = [0u32, 63, 64, 16383, 16384, 1073741823, 1073741824, u32::max_value()];
+
+		for &n in &compacts {
+			for len in &[0usize, 1, 63, 64] {
+				let value = (Compact(n), vec![7u8; *len]);
+				let encoded = value.encode();
+
+				let compact_len = <Compact<u32> as crate::CompactLen<u32>>::compact_len(&n);
+				assert_eq!(&encoded[..compact_len], &Compact(n).encode()[..]);
+				assert_eq!(&encoded[compact_len..], &value.1.encode()[..]);
+				assert_eq!(value.size_hint(), Compact(n).size_hint() + value.1.size_hint());
+				assert!(value.size_hint() >= encoded.len());
+
+				let decoded = <(Compact<u32>, Vec<u8>)>::decode(&mut &encoded[..]).unwrap();
+				assert_eq!(decoded, value);
+			}
+		}
+
+		let value = (Compact(16384u32), vec![1u8, 2, 3], Compact(64u64));
+		let encoded = value.encode();
+		assert_eq!(hexify(&encoded), "02 00 01 00 0c 01 02 03 01 01");
+		assert_eq!(<(Compact<u32>, Vec<u8>, Compact<u64>)>::decode(&mut &encoded[..]), Ok(value));
+	}
+
+	#[test]
+	fn decode_bytes_into_borrowed_buffer() {
+		let data: Vec<u8> = (0..40).collect();
+		let mut input = &data[..];
+
+		let mut buf = [0u8; 32];
+		input.decode_bytes_into(&mut buf[..]).unwrap();
+		assert_eq!(&buf[..], &data[..32]);
+		assert_eq!(input, &data[32..]);
+
+		assert!(input.decode_bytes_into(&mut buf[..]).is_err());
+
+		assert_eq!(<[u8; 32]>::decode(&mut &data[..]).unwrap(), buf);
+		assert_eq!(<[i8; 4]>::decode(&mut &[1, 2, 0xff, 0xfe][..]).unwrap(), [1, 2, -1, -2]);
+		assert!(<[u8; 32]>::decode(&mut &data[..31]).is_err());
+	}
+
+	#[test]
+	fn failed_scan_leaves_input_untouched() {
+		// A `Vec<u32>` of length 2 followed by a single byte: decoding it as `(Vec<u16>, u32, u16)`
+		// consumes bytes before failing.
+		let encoded = (vec![0x0102_0304u32, 0x0506_0708], 9u8).encode();
+		let mut input = &encoded[..];
+
+		assert!(scan(&mut input, |i| <(Vec<u16>, u32, u16)>::decode(i)).is_err());
+		assert_eq!(input, &encoded[..]);
+
+		assert_eq!(scan(&mut input, |i| Vec::<u32>::decode(i)), Ok(vec![0x0102_0304, 0x0506_0708]));
+		assert_eq!(input, &[9]);
+		assert_eq!(u8::decode(&mut input), Ok(9));
+	}
+
+	#[test]
+	#[cfg(feature = "bytes")]
+	fn bytes_works_same_as_vec() {
+		let data = vec![1u8, 2, 3, 4, 5];
+		let encoded = data.encode();
+
+		assert_eq!(bytes::Bytes::from(data.clone()).encode(), encoded);
+		assert_eq!(bytes::BytesMut::from(&data[..]).encode(), encoded);
+		assert_eq!(bytes::Bytes::decode(&mut &encoded[..]).unwrap(), &data[..]);
+		assert_eq!(bytes::BytesMut::decode(&mut &encoded[..]).unwrap(), &data[..]);
+		assert!(bytes::Bytes::decode(&mut &encoded[..3]).is_err());
+	}
+
+	#[test]
+	#[cfg(feature = "bytes")]
+	fn decode_from_bytes_shares_the_buffer() {
+		let encoded = bytes::Bytes::from((42u32, vec![7u8; 64], vec![8u8; 16]).encode());
+
+		let (n, first, second) =
+			super::decode_from_bytes::<(u32, bytes::Bytes, bytes::Bytes)>(encoded.clone()).unwrap();
+		assert_eq!(n, 42);
+		assert_eq!(first, vec![7u8; 64]);
+		assert_eq!(second, vec![8u8; 16]);
+
+		// 4 bytes for the `u32` and 2 bytes for the compact length of 64.
+		assert_eq!(first.as_ptr(), encoded[6..].as_ptr());
+		// Another byte for the compact length of 16.
+		assert_eq!(second.as_ptr(), encoded[(6 + 64 + 1)..].as_ptr());
+
+		assert!(super::decode_from_bytes::<bytes::Bytes>(encoded.slice(..5)).is_err());
+	}
+
+	#[test]
+	fn atomics_encode_like_their_value() {
+		use core::sync::atomic::*;
+
+		macro_rules! check_atomic {
+			( $( $name:ident: $value:expr ),* $(,)? ) => {
+				$(
+					let atomic = $name::new($value);
+					let encoded = atomic.encode();
+					assert_eq!(encoded, $value.encode());
+					assert_eq!($name::decode(&mut &encoded[..]).unwrap().into_inner(), $value);
+				)*
+			}
+		}
+
+		check_atomic! {
+			AtomicBool: true,
+			AtomicI8: -8i8,
+			AtomicI16: -16i16,
+			AtomicI32: -32i32,
+			AtomicI64: i64::min_value(),
+			AtomicU8: 8u8,
+			AtomicU16: 16u16,
+			AtomicU32: 32u32,
+			AtomicU64: u64::max_value(),
+		}
+
+		assert!(AtomicBool::decode(&mut &[2][..]).is_err());
+		assert!(AtomicU32::decode(&mut &[1, 2, 3][..]).is_err());
+	}
+
+	#[test]
+	fn cstring_works() {
+		use std::ffi::CString;
+
+		let value = CString::new("hello").unwrap();
+		let encoded = value.encode();
+		assert_eq!(encoded, b"hello".to_vec().encode());
+		assert_eq!(CString::decode(&mut &encoded[..]).unwrap(), value);
+
+		let empty = CString::new("").unwrap();
+		assert_eq!(empty.encode(), vec![0]);
+		assert_eq!(CString::decode(&mut &[0][..]).unwrap(), empty);
+	}
+
+	#[test]
+	fn cstring_with_interior_nul_fails_to_decode() {
+		let encoded = b"hel\0lo".to_vec().encode();
+		assert_eq!(
+			std::ffi::CString::decode(&mut &encoded[..]).err().unwrap().to_string(),
+			"Interior nul byte in `CString`",
+		);
+	}
+
+	#[test]
+	fn locks_encode_their_value() {
+		use std::sync::{Mutex, RwLock};
+
+		let value = (5u32, vec![1u8, 2]);
+		let mutex = Mutex::new(value.clone());
+		let encoded = mutex.encode();
+		assert_eq!(encoded, value.encode());
+		assert_eq!(mutex.size_hint(), value.size_hint());
+		assert_eq!(Mutex::<(u32, Vec<u8>)>::decode(&mut &encoded[..]).unwrap().into_inner().unwrap(), value);
+
+		let rw_lock = RwLock::new(value.clone());
+		let encoded = rw_lock.encode();
+		assert_eq!(encoded, value.encode());
+		assert_eq!(RwLock::<(u32, Vec<u8>)>::decode(&mut &encoded[..]).unwrap().into_inner().unwrap(), value);
+
+		assert_eq!(try_encode_mutex(&mutex), Ok(value.encode()));
+		assert_eq!(try_encode_rw_lock(&rw_lock), Ok(value.encode()));
+
+		assert!(Mutex::<u32>::decode(&mut &[1, 2][..]).is_err());
+		assert!(RwLock::<u32>::decode(&mut &[1, 2][..]).is_err());
+	}
+
+	#[test]
+	fn poisoned_locks_encode_their_value() {
+		use std::sync::{Mutex, RwLock};
+
+		let mutex = Mutex::new(7u16);
+		let rw_lock = RwLock::new(7u16);
+		let _ = std::panic::catch_unwind(|| {
+			let _mutex_guard = mutex.lock().unwrap();
+			let _rw_lock_guard = rw_lock.write().unwrap();
+			panic!("poison the locks");
+		});
+		assert!(mutex.is_poisoned());
+		assert!(rw_lock.is_poisoned());
+
+		assert_eq!(mutex.encode(), 7u16.encode());
+		assert_eq!(rw_lock.encode(), 7u16.encode());
+
+		assert_eq!(
+			try_encode_mutex(&mutex),
+			Err("Attempted to encode a poisoned `Mutex`".into()),
+		);
+		assert_eq!(
+			try_encode_rw_lock(&rw_lock),
+			Err("Attempted to encode a poisoned `RwLock`".into()),
+		);
+	}
+
+	#[test]
+	fn borrowed_slice_decodes_as_boxed_slice() {
+		let slice: &[u16] = &[1, 2, 3, 0xffff];
+		let encoded = slice.encode();
+
+		let boxed = <Box<[u16]>>::decode(&mut &encoded[..]).unwrap();
+		assert_eq!(&*boxed, slice);
+		assert_eq!(boxed.encode(), encoded);
+		assert_eq!(<Box<[u16]>>::decode(&mut &Vec::<u16>::new().encode()[..]).unwrap().len(), 0);
+
+		fn encode_like<T: Encode, R: EncodeLike<T>>(value: &R) -> Vec<u8> {
+			value.encode()
+		}
+		assert_eq!(encode_like::<Box<[u16]>, _>(&slice), encoded);
+		assert_eq!(encode_like::<&[u16], _>(&boxed), encoded);
+	}
+
+	#[test]
+	fn boxed_str_encodes_like_string() {
+		for string in &["", "hello", "\u{1f600} wide"] {
+			let boxed: Box<str> = (*string).into();
+			let encoded = boxed.encode();
+			assert_eq!(encoded, string.encode());
+			assert_eq!(<Box<str>>::decode(&mut &encoded[..]), Ok(boxed));
+		}
+
+		assert!(<Box<str>>::decode(&mut &vec![0xffu8, 0xfe].encode()[..]).is_err());
+
+		fn encode_like<T: Encode, R: EncodeLike<T>>(value: &R) -> Vec<u8> {
+			value.encode()
+		}
+		let boxed: Box<str> = "boxed".into();
+		assert_eq!(encode_like::<String, _>(&boxed), "boxed".encode());
+		assert_eq!(encode_like::<Box<str>, _>(&"boxed".to_owned()), "boxed".encode());
+	}
+
+	#[test]
+	fn shared_slice_and_str_roundtrip() {
+		use std::rc::Rc;
+
+		let slice: Arc<[u32]> = vec![1, 2, 0xffff_ffff].into();
+		let encoded = slice.encode();
+		assert_eq!(encoded, vec![1u32, 2, 0xffff_ffff].encode());
+		assert_eq!(<Arc<[u32]>>::decode(&mut &encoded[..]), Ok(slice));
+		assert_eq!(<Rc<[u32]>>::decode(&mut &encoded[..]).unwrap().len(), 3);
+		assert_eq!(<Arc<[u32]>>::decode(&mut &[0][..]).unwrap().len(), 0);
+
+		let string: Arc<str> = "shared".into();
+		let encoded = string.encode();
+		assert_eq!(encoded, "shared".encode());
+		assert_eq!(<Arc<str>>::decode(&mut &encoded[..]), Ok(string));
+		assert_eq!(&*<Rc<str>>::decode(&mut &encoded[..]).unwrap(), "shared");
+		assert!(<Arc<str>>::decode(&mut &vec![0xffu8].encode()[..]).is_err());
+	}
+
+	#[test]
+	fn wrapping_encodes_like_inner() {
+		for value in &[i64::min_value(), -1, 0, i64::max_value()] {
+			let wrapping = Wrapping(*value);
+			let encoded = wrapping.encode();
+			assert_eq!(encoded, value.to_le_bytes());
+			assert_eq!(Wrapping::<i64>::decode(&mut &encoded[..]).unwrap(), wrapping);
+		}
+
+		assert_eq!(Wrapping(-1i32).encode(), vec![0xff; 4]);
+		assert_eq!(Wrapping::<i32>::decode(&mut &[0xff; 4][..]).unwrap(), Wrapping(-1));
+		assert_eq!(Wrapping(u16::max_value()).encode(), vec![0xff; 2]);
+	}
+
+	#[test]
+	fn integer_slices_encode_like_their_elements() {
+		macro_rules! check {
+			( $( $ty:ty ),* ) => {$(
+				let values: Vec<$ty> = (0..100u8).map(|i| <$ty>::max_value() / 101 * i as $ty).collect();
+
+				let mut expected = Compact(values.len() as u32).encode();
+				for value in &values {
+					expected.extend_from_slice(&value.to_le_bytes());
+				}
+
+				let mut one_by_one = Compact(values.len() as u32).encode();
+				for value in &values {
+					value.encode_to(&mut one_by_one);
+				}
+
+				assert_eq!(values.encode(), expected, stringify!($ty));
+				assert_eq!(values[..].encode(), one_by_one, stringify!($ty));
+				assert_eq!(<Vec<$ty>>::decode(&mut &expected[..]).unwrap(), values);
+			)*};
+		}
+
+		check!(u16, u32, u64, u128, i16, i32, i64, i128);
+	}
+
+	#[test]
+	fn decode_vec_exact_reads_only_len_elements() {
+		let data = [1u8, 0, 2, 0, 3, 0, 4];
+		let mut input = &data[..];
+		assert_eq!(decode_vec_exact::<u16, _>(3, &mut input), Ok(vec![1, 2, 3]));
+		assert_eq!(input, &[4]);
+
+		assert_eq!(decode_vec_exact::<u16, _>(0, &mut &data[..]), Ok(vec![]));
+		assert!(decode_vec_exact::<u16, _>(4, &mut &data[..]).is_err());
+		assert!(decode_vec_exact::<u16, _>(usize::max_value(), &mut &data[..]).is_err());
+
+		let strings = ("a", "bc").encode();
+		assert_eq!(decode_vec_exact::<String, _>(2, &mut &strings[..]), Ok(vec!["a".into(), "bc".into()]));
+	}
+
+	#[test]
+	fn decode_versioned_checks_version() {
+		let records = vec![(1u32, "a".to_string()), (2, "bc".to_string())];
+		let encoded = (3u8, &records).encode();
+
+		let mut input = &encoded[..];
+		assert_eq!(decode_versioned::<(u32, String), _>(3, &mut input), Ok(records));
+		assert!(input.is_empty());
+
+		assert_eq!(
+			decode_versioned::<(u32, String), _>(4, &mut &encoded[..]).err().unwrap().to_string(),
+			"Unexpected version byte",
+		);
+		assert!(decode_versioned::<u32, _>(3, &mut &[][..]).is_err());
+		assert!(decode_versioned::<u32, _>(3, &mut &[3, 4, 1][..]).is_err());
+	}
+
+	#[test]
+	fn encode_iter_matches_vec_encoding() {
+		let mut encoded = Vec::new();
+		encode_iter((0..100u32).map(|i| i * 3), &mut encoded);
+		assert_eq!(encoded, (0..100u32).map(|i| i * 3).collect::<Vec<_>>().encode());
+
+		let strings = ["a", "bc", ""];
+		let mut encoded = Vec::new();
+		encode_iter(strings.iter(), &mut encoded);
+		assert_eq!(encoded, strings.to_vec().encode());
+		assert_eq!(<Vec<String>>::decode(&mut &encoded[..]).unwrap(), strings);
+
+		let mut encoded = Vec::new();
+		encode_iter(core::iter::empty::<u64>(), &mut encoded);
+		assert_eq!(encoded, vec![0]);
+	}
+
+	#[test]
+	fn encode_to_yielding_counts_yields() {
+		let items: Vec<u16> = (0..10).collect();
+
+		for &(every, expected_yields) in &[(1, 10), (3, 3), (5, 2), (10, 1), (11, 0)] {
+			let mut yields = 0;
+			let mut encoded = Vec::new();
+			encode_to_yielding(&items, &mut encoded, every, || yields += 1);
+			assert_eq!(encoded, items.encode());
+			assert_eq!(yields, expected_yields, "yielding every {} items", every);
+		}
+
+		let mut yields = 0;
+		let mut encoded = Vec::new();
+		encode_to_yielding(&[] as &[u8], &mut encoded, 1, || yields += 1);
+		assert_eq!((encoded, yields), (vec![0], 0));
+	}
+
+	#[test]
+	fn btree_set_decoding_is_canonical() {
+		let set: BTreeSet<u32> = vec![3, 1, 2].into_iter().collect();
+		let encoded = set.encode();
+		assert_eq!(encoded, vec![1u32, 2, 3].encode());
+		assert_eq!(BTreeSet::decode(&mut &encoded[..]), Ok(set));
+		assert_eq!(BTreeSet::<u32>::decode(&mut &[0][..]), Ok(BTreeSet::new()));
+
+		// Out of order.
+		assert!(BTreeSet::<u32>::decode(&mut &vec![1u32, 3, 2].encode()[..]).is_err());
+		assert!(BTreeSet::<String>::decode(&mut &vec!["b", "a"].encode()[..]).is_err());
+		// Duplicated.
+		assert!(BTreeSet::<u32>::decode(&mut &vec![1u32, 2, 2].encode()[..]).is_err());
+	}
+
+	#[test]
+	fn hash_set_encoding_is_deterministic() {
+		use std::collections::HashSet;
+
+		let empty = HashSet::<u32>::new();
+		assert_eq!(empty.encode(), vec![0]);
+		assert_eq!(HashSet::<u32>::decode(&mut &[0][..]), Ok(empty));
+
+		let single: HashSet<String> = vec!["one".to_owned()].into_iter().collect();
+		assert_eq!(single.encode(), vec!["one"].encode());
+		assert_eq!(HashSet::decode(&mut &single.encode()[..]), Ok(single));
+
+		// Sorted by encoding, i.e. by the little endian bytes.
+		let a: HashSet<u16> = vec![0x0102, 0x0201, 3, 0x0300].into_iter().collect();
+		let b: HashSet<u16> = vec![0x0300, 3, 0x0201, 0x0102].into_iter().collect();
+		let encoded = a.encode();
+		assert_eq!(encoded, b.encode());
+		assert_eq!(encoded, vec![0x0300u16, 0x0201, 0x0102, 3].encode());
+		assert!(encoded.len() <= a.size_hint());
+		assert_eq!(HashSet::decode(&mut &encoded[..]), Ok(a));
+	}
+
+	#[test]
+	fn hash_set_rejects_duplicates() {
+		let encoded = vec![1u8, 2, 1].encode();
+		assert!(std::collections::HashSet::<u8>::decode(&mut &encoded[..]).is_err());
+	}
+
+	#[test]
+	fn hash_map_encoding_is_deterministic() {
+		use std::collections::{BTreeMap, HashMap};
+
+		let btree: BTreeMap<u8, String> = vec![(3, "c".to_owned()), (1, "a".to_owned()), (2, "b".to_owned())]
+			.into_iter()
+			.collect();
+		let a: HashMap<u8, String> = btree.clone().into_iter().collect();
+		let b: HashMap<u8, String> = btree.clone().into_iter().rev().collect();
+		let encoded = a.encode();
+		assert_eq!(encoded, b.encode());
+		assert_eq!(encoded, btree.encode());
+		assert_eq!(HashMap::decode(&mut &encoded[..]), Ok(a));
+
+		assert!(HashMap::<u8, u8>::decode(&mut &vec![(1u8, 2u8), (1, 3)].encode()[..]).is_err());
+	}
+
+	#[test]
+	fn hash_map_keys_are_ordered_by_their_encoding() {
+		use std::collections::HashMap;
+
+		let map: HashMap<i32, u8> = vec![(-1, 0), (1, 1), (-256, 2), (256, 3), (0, 4)].into_iter().collect();
+		let encoded = map.encode();
+		let mut expected = vec![20];
+		for (key, value) in &[(0i32, 4u8), (256, 3), (-256, 2), (1, 1), (-1, 0)] {
+			expected.extend(key.encode());
+			expected.push(*value);
+		}
+		assert_eq!(encoded, expected);
+		assert_eq!(HashMap::decode(&mut &encoded[..]), Ok(map));
+
+		let map: HashMap<u16, ()> = vec![(1, ()), (256, ())].into_iter().collect();
+		assert_eq!(map.encode(), vec![8, 0, 1, 1, 0]);
+	}
+
+	#[test]
+	fn hash_map_with_custom_hasher() {
+		use std::collections::{HashMap, hash_map::DefaultHasher};
+		use std::hash::BuildHasherDefault;
+
+		type CustomMap<K, V> = HashMap<K, V, BuildHasherDefault<DefaultHasher>>;
+
+		let mut map = CustomMap::default();
+		map.insert(10u32, vec![1u8, 2]);
+		map.insert(20u32, vec![]);
+		let encoded = map.encode();
+		assert_eq!(encoded, vec![(10u32, vec![1u8, 2]), (20, vec![])].encode());
+		assert_eq!(CustomMap::decode(&mut &encoded[..]), Ok(map.clone()));
+
+		let std_map: HashMap<u32, Vec<u8>> = map.clone().into_iter().collect();
+		assert_eq!(std_map.encode(), encoded);
+	}
+
+	#[test]
+	fn control_flow_roundtrip() {
+		let cont: ControlFlow<u8, u32> = ControlFlow::Continue(0x0102_0304);
+		let encoded = cont.encode();
+		assert_eq!(encoded, vec![0, 4, 3, 2, 1]);
+		assert_eq!(encoded.len(), cont.size_hint());
+		assert_eq!(ControlFlow::<u8, u32>::decode(&mut &encoded[..]), Ok(cont));
+
+		let brk: ControlFlow<String, ()> = ControlFlow::Break("done".to_owned());
+		let encoded = brk.encode();
+		assert_eq!(encoded, (1u8, "done").encode());
+		assert_eq!(ControlFlow::<String, ()>::decode(&mut &encoded[..]), Ok(brk));
+
+		assert!(ControlFlow::<u8, u8>::decode(&mut &[2, 0][..]).is_err());
+		assert!(ControlFlow::<u8, u32>::decode(&mut &[0, 1][..]).is_err());
+	}
+
+	#[test]
+	fn infallible_works() {
+		let ok: Result<u32, Infallible> = Ok(5);
+		let encoded = ok.encode();
+		assert_eq!(encoded, (0u8, 5u32).encode());
+		assert_eq!(<Result<u32, Infallible>>::decode(&mut &encoded[..]), Ok(ok));
+
+		assert!(Infallible::decode(&mut &[][..]).is_err());
+		assert!(<Result<u32, Infallible>>::decode(&mut &[1][..]).is_err());
+	}
+
 	#[test]
 	fn ranges() {
 		let range = Range { start: 1, end: 100 };
@@ -1695,5 +3355,23 @@ mod tests {
 		let range_inclusive_bytes = (1, 100).encode();
 		assert_eq!(range_inclusive.encode(), range_inclusive_bytes);
 		assert_eq!(RangeInclusive::decode(&mut &range_inclusive_bytes[..]), Ok(range_inclusive));
+
+		let range_from = RangeFrom { start: 7u32 };
+		let range_from_bytes = 7u32.encode();
+		assert_eq!(range_from.encode(), range_from_bytes);
+		assert_eq!(RangeFrom::decode(&mut &range_from_bytes[..]), Ok(range_from));
+
+		let range_to = RangeTo { end: 7u64 };
+		let range_to_bytes = 7u64.encode();
+		assert_eq!(range_to.encode(), range_to_bytes);
+		assert_eq!(RangeTo::decode(&mut &range_to_bytes[..]), Ok(range_to));
+
+		let range_to_inclusive = ..=-3i16;
+		let range_to_inclusive_bytes = (-3i16).encode();
+		assert_eq!(range_to_inclusive.encode(), range_to_inclusive_bytes);
+		assert_eq!(RangeToInclusive::decode(&mut &range_to_inclusive_bytes[..]), Ok(range_to_inclusive));
+
+		assert!(RangeFull.encode().is_empty());
+		assert_eq!(RangeFull::decode(&mut &[][..]), Ok(RangeFull));
 	}
 }
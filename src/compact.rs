@@ -43,6 +43,45 @@ impl<const N: usize> Output for ArrayVecWrapper<N> {
 	}
 }
 
+/// Output wrapper that checks, in debug builds, that a compact value is encoded in its minimal
+/// mode.
+///
+/// Every mode has a distinct encoded length, so the check compares the number of written bytes
+/// against [`CompactLen::compact_len`].
+#[cfg(debug_assertions)]
+struct MinimalModeCheck<'a, W: ?Sized> {
+	dest: &'a mut W,
+	written: usize,
+}
+
+#[cfg(debug_assertions)]
+impl<'a, W: Output + ?Sized> MinimalModeCheck<'a, W> {
+	fn new(dest: &'a mut W) -> Self {
+		Self { dest, written: 0 }
+	}
+
+	fn check(&self, expected: usize) {
+		debug_assert_eq!(
+			self.written,
+			expected,
+			"Compact value was not encoded in its minimal mode",
+		);
+	}
+}
+
+#[cfg(debug_assertions)]
+impl<'a, W: Output + ?Sized> Output for MinimalModeCheck<'a, W> {
+	fn write(&mut self, bytes: &[u8]) {
+		self.written += bytes.len();
+		self.dest.write(bytes);
+	}
+
+	fn push_byte(&mut self, byte: u8) {
+		self.written += 1;
+		self.dest.push_byte(byte);
+	}
+}
+
 /// Prefix another input with a byte.
 struct PrefixInput<'a, T> {
 	prefix: Option<u8>,
@@ -77,6 +116,8 @@ pub trait CompactLen<T> {
 }
 
 /// Compact-encoded variant of T. This is more space-efficient but less compute-efficient.
+///
+/// Decoding is strict: a value encoded in a wider mode than necessary is rejected.
 #[derive(Eq, PartialEq, Clone, Copy, Ord, PartialOrd)]
 #[cfg_attr(feature = "fuzz", derive(Arbitrary))]
 pub struct Compact<T>(pub T);
@@ -178,6 +219,317 @@ macro_rules! impl_from_compact {
 
 impl_from_compact! { (), u8, u16, u32, u64, u128 }
 
+/// A duration in whole seconds, which can be compact encoded as `Compact<Seconds>`.
+///
+/// The compact encoding of the seconds count takes 1 byte up to 63 seconds and 4 bytes up to
+/// about 34 years, where a `Duration` always takes 12 bytes. The sub-second part of a `Duration`
+/// converted into `Seconds` is dropped, so only durations with whole seconds round-trip.
+///
+/// ```
+/// # use core::time::Duration;
+/// # use parity_scale_codec::{Compact, Encode, Seconds};
+/// let timeout = Seconds::from(Duration::from_millis(30_500));
+/// assert_eq!(Compact(timeout).encode(), Compact(30u64).encode());
+/// assert_eq!(Duration::from(timeout), Duration::from_secs(30));
+/// ```
+#[derive(Debug, Default, Eq, PartialEq, Clone, Copy, Ord, PartialOrd, Hash)]
+pub struct Seconds(u64);
+
+impl Seconds {
+	/// Create a new instance from a number of seconds.
+	pub fn new(secs: u64) -> Self {
+		Seconds(secs)
+	}
+
+	/// The number of seconds.
+	pub fn as_secs(&self) -> u64 {
+		self.0
+	}
+}
+
+impl From<core::time::Duration> for Seconds {
+	fn from(duration: core::time::Duration) -> Self {
+		Seconds(duration.as_secs())
+	}
+}
+
+impl From<Seconds> for core::time::Duration {
+	fn from(secs: Seconds) -> Self {
+		core::time::Duration::from_secs(secs.0)
+	}
+}
+
+impl CompactAs for Seconds {
+	type As = u64;
+
+	fn encode_as(&self) -> &u64 {
+		&self.0
+	}
+
+	fn decode_from(secs: u64) -> Result<Self, Error> {
+		Ok(Seconds(secs))
+	}
+}
+
+impl From<Compact<Seconds>> for Seconds {
+	fn from(x: Compact<Seconds>) -> Seconds {
+		x.0
+	}
+}
+
+/// A `Duration` encoded as its total number of nanoseconds in a `Compact<u128>`.
+///
+/// Unlike [`Seconds`], this keeps the full precision of the `Duration`. It takes 1 byte up to
+/// 63ns, 4 bytes up to about 1s and 8 bytes up to about 2 years, where a `Duration` always takes
+/// 12 bytes.
+///
+/// The number of nanoseconds of any `Duration` fits in a `u128`, so encoding never overflows.
+/// Decoding fails if the number of nanoseconds is too large for a `Duration`, i.e. if the number
+/// of seconds doesn't fit in a `u64`.
+///
+/// ```
+/// # use core::time::Duration;
+/// # use parity_scale_codec::{Compact, CompactDuration, Encode};
+/// let timer = CompactDuration(Duration::from_micros(15));
+/// assert_eq!(timer.encode(), Compact(15_000u128).encode());
+/// ```
+#[derive(Debug, Default, Eq, PartialEq, Clone, Copy, Ord, PartialOrd, Hash)]
+pub struct CompactDuration(pub core::time::Duration);
+
+impl From<core::time::Duration> for CompactDuration {
+	fn from(duration: core::time::Duration) -> Self {
+		CompactDuration(duration)
+	}
+}
+
+impl From<CompactDuration> for core::time::Duration {
+	fn from(duration: CompactDuration) -> Self {
+		duration.0
+	}
+}
+
+impl Encode for CompactDuration {
+	fn size_hint(&self) -> usize {
+		Compact::compact_len(&self.0.as_nanos())
+	}
+
+	fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+		Compact(self.0.as_nanos()).encode_to(dest)
+	}
+}
+
+impl EncodeLike for CompactDuration {}
+
+impl Decode for CompactDuration {
+	fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+		const NANOS_PER_SEC: u128 = 1_000_000_000;
+
+		let nanos = Compact::<u128>::decode(input)?.0;
+		let secs: u64 = core::convert::TryFrom::try_from(nanos / NANOS_PER_SEC)
+			.map_err(|_| "Number of nanoseconds of `CompactDuration` out of range")?;
+		Ok(CompactDuration(core::time::Duration::new(secs, (nanos % NANOS_PER_SEC) as u32)))
+	}
+}
+
+/// A signed integer zig-zag encoded into a compact unsigned integer.
+///
+/// **This is an extension format, not part of SCALE.** Signed integers are always encoded at
+/// their full width, and `Compact` only supports unsigned integers. `Delta` maps values close to
+/// zero, negative or not, to small unsigned numbers (`0, -1, 1, -2, 2, ...` become
+/// `0, 1, 2, 3, 4, ...`) and encodes those as a `Compact` of the unsigned integer of the same
+/// width. This suits series of small differences between successive values.
+///
+/// ```
+/// # use parity_scale_codec::{Compact, Delta, Encode};
+/// assert_eq!(Delta(-1i64).encode(), Compact(1u64).encode());
+/// assert_eq!(Delta(1i64).encode(), Compact(2u64).encode());
+/// assert_eq!(Delta(i64::min_value()).encode(), Compact(u64::max_value()).encode());
+/// ```
+#[derive(Debug, Default, Eq, PartialEq, Clone, Copy, Ord, PartialOrd, Hash)]
+pub struct Delta<T>(pub T);
+
+macro_rules! impl_delta {
+	( $( $signed:ty => $unsigned:ty, )* ) => { $(
+		impl From<$signed> for Delta<$signed> {
+			fn from(x: $signed) -> Self {
+				Delta(x)
+			}
+		}
+
+		impl Delta<$signed> {
+			/// The zig-zag mapping of the value, which is compact encoded.
+			fn zig_zag(self) -> $unsigned {
+				((self.0 << 1) ^ (self.0 >> (<$signed>::BITS - 1))) as $unsigned
+			}
+
+			/// The inverse of `zig_zag`.
+			fn from_zig_zag(x: $unsigned) -> Self {
+				Delta(((x >> 1) as $signed) ^ -((x & 1) as $signed))
+			}
+		}
+
+		impl Encode for Delta<$signed> {
+			fn size_hint(&self) -> usize {
+				Compact::compact_len(&self.zig_zag())
+			}
+
+			fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+				Compact(self.zig_zag()).encode_to(dest)
+			}
+		}
+
+		impl EncodeLike for Delta<$signed> {}
+
+		impl Decode for Delta<$signed> {
+			fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+				Compact::<$unsigned>::decode(input).map(|x| Self::from_zig_zag(x.0))
+			}
+		}
+
+		#[cfg(feature = "max-encoded-len")]
+		impl crate::MaxEncodedLen for Delta<$signed> {
+			fn max_encoded_len() -> usize {
+				<Compact<$unsigned> as crate::MaxEncodedLen>::max_encoded_len()
+			}
+		}
+	)* }
+}
+
+impl_delta! {
+	i8 => u8,
+	i16 => u16,
+	i32 => u32,
+	i64 => u64,
+	i128 => u128,
+}
+
+/// An unsigned integer compact encoded in the given mode, even if a shorter one would fit.
+///
+/// **This is not canonical SCALE on purpose.** `Compact` always uses the shortest mode, and its
+/// decoding rejects any other. `CompactFixed` is meant for parsers expecting a fixed width:
+/// `MODE` is the value of the two lowest bits of the first byte:
+/// * `0`: single byte mode, for values up to `2^6 - 1`.
+/// * `1`: two bytes mode, for values up to `2^14 - 1`.
+/// * `2`: four bytes mode, for values up to `2^30 - 1`.
+/// * `3`: big integer mode, using at least 4 bytes after the length byte.
+///
+/// Values which don't fit in `MODE` are rejected by [`CompactFixed::new`], and decoding fails if
+/// the encoded mode is not `MODE`.
+///
+/// ```
+/// # use parity_scale_codec::{CompactFixed, Decode, Encode};
+/// let fixed = CompactFixed::<u32, 2>::new(1).unwrap();
+/// assert_eq!(fixed.encode(), vec![0b0000_0110, 0, 0, 0]);
+/// assert_eq!(CompactFixed::<u32, 2>::decode(&mut &[0b0000_0110, 0, 0, 0][..]), Ok(fixed));
+/// assert!(CompactFixed::<u32, 0>::new(64).is_err());
+/// ```
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Ord, PartialOrd, Hash)]
+pub struct CompactFixed<T, const MODE: u8>(T);
+
+impl<T: Copy + Into<u128>, const MODE: u8> CompactFixed<T, MODE> {
+	/// Create a new instance, failing if `value` doesn't fit in `MODE` or if `MODE` is invalid.
+	pub fn new(value: T) -> Result<Self, Error> {
+		let fits = match MODE {
+			0 => value.into() < 1 << 6,
+			1 => value.into() < 1 << 14,
+			2 => value.into() < 1 << 30,
+			3 => true,
+			_ => return Err("Invalid compact mode".into()),
+		};
+		if fits {
+			Ok(CompactFixed(value))
+		} else {
+			Err("Value doesn't fit in the compact mode".into())
+		}
+	}
+
+	/// The encoded value.
+	pub fn get(&self) -> T {
+		self.0
+	}
+
+	/// The number of bytes of the value in big integer mode, without the length byte.
+	fn big_integer_len(value: u128) -> usize {
+		(128 - value.leading_zeros() as usize).div_ceil(8).max(4)
+	}
+}
+
+impl<T: Copy + Into<u128>, const MODE: u8> Encode for CompactFixed<T, MODE> {
+	fn size_hint(&self) -> usize {
+		match MODE {
+			0 => 1,
+			1 => 2,
+			2 => 4,
+			_ => 1 + Self::big_integer_len(self.0.into()),
+		}
+	}
+
+	fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+		let value = self.0.into();
+		match MODE {
+			0 => dest.push_byte((value as u8) << 2),
+			1 => (((value as u16) << 2) | 0b01).encode_to(dest),
+			2 => (((value as u32) << 2) | 0b10).encode_to(dest),
+			_ => {
+				let len = Self::big_integer_len(value);
+				dest.push_byte((((len - 4) as u8) << 2) | 0b11);
+				dest.write(&value.to_le_bytes()[..len]);
+			},
+		}
+	}
+}
+
+impl<T: Copy + Into<u128>, const MODE: u8> EncodeLike for CompactFixed<T, MODE> {}
+
+impl<T, const MODE: u8> Decode for CompactFixed<T, MODE> where
+	T: Copy + Into<u128> + core::convert::TryFrom<u128>,
+{
+	fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+		let prefix = input.read_byte()?;
+		if prefix & 0b11 != MODE {
+			return Err("Unexpected compact mode".into());
+		}
+
+		let value = match MODE {
+			0 => u128::from(prefix >> 2),
+			1 => u128::from(u16::from_le_bytes([prefix, input.read_byte()?]) >> 2),
+			2 => {
+				let mut bytes = [prefix, 0, 0, 0];
+				input.read(&mut bytes[1..])?;
+				u128::from(u32::from_le_bytes(bytes) >> 2)
+			},
+			_ => {
+				let len = (prefix >> 2) as usize + 4;
+				if len > 16 {
+					return Err("Compact big integer is too long".into());
+				}
+				let mut bytes = [0; 16];
+				input.read(&mut bytes[..len])?;
+				let value = u128::from_le_bytes(bytes);
+				if Self::big_integer_len(value) != len {
+					return Err("Compact big integer is longer than needed".into());
+				}
+				value
+			},
+		};
+
+		let value = T::try_from(value).map_err(|_| "Compact value out of range")?;
+		Ok(CompactFixed(value))
+	}
+}
+
+#[cfg(feature = "max-encoded-len")]
+impl<T: Copy + Into<u128>, const MODE: u8> crate::MaxEncodedLen for CompactFixed<T, MODE> {
+	fn max_encoded_len() -> usize {
+		match MODE {
+			0 => 1,
+			1 => 2,
+			2 => 4,
+			_ => 1 + core::mem::size_of::<T>().max(4),
+		}
+	}
+}
+
 /// Compact-encoded variant of &'a T. This is more space-efficient but less compute-efficient.
 #[derive(Eq, PartialEq, Clone, Copy)]
 pub struct CompactRef<'a, T>(pub &'a T);
@@ -241,10 +593,16 @@ impl<'a> Encode for CompactRef<'a, u8> {
 	}
 
 	fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+		#[cfg(debug_assertions)]
+		let dest = &mut MinimalModeCheck::new(dest);
+
 		match self.0 {
 			0..=0b0011_1111 => dest.push_byte(self.0 << 2),
 			_ => ((u16::from(*self.0) << 2) | 0b01).encode_to(dest),
 		}
+
+		#[cfg(debug_assertions)]
+		dest.check(Compact::compact_len(self.0));
 	}
 
 	fn using_encoded<R, F: FnOnce(&[u8]) -> R>(&self, f: F) -> R {
@@ -269,11 +627,17 @@ impl<'a> Encode for CompactRef<'a, u16> {
 	}
 
 	fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+		#[cfg(debug_assertions)]
+		let dest = &mut MinimalModeCheck::new(dest);
+
 		match self.0 {
 			0..=0b0011_1111 => dest.push_byte((*self.0 as u8) << 2),
 			0..=0b0011_1111_1111_1111 => ((*self.0 << 2) | 0b01).encode_to(dest),
 			_ => ((u32::from(*self.0) << 2) | 0b10).encode_to(dest),
 		}
+
+		#[cfg(debug_assertions)]
+		dest.check(Compact::compact_len(self.0));
 	}
 
 	fn using_encoded<R, F: FnOnce(&[u8]) -> R>(&self, f: F) -> R {
@@ -299,6 +663,9 @@ impl<'a> Encode for CompactRef<'a, u32> {
 	}
 
 	fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+		#[cfg(debug_assertions)]
+		let dest = &mut MinimalModeCheck::new(dest);
+
 		match self.0 {
 			0..=0b0011_1111 => dest.push_byte((*self.0 as u8) << 2),
 			0..=0b0011_1111_1111_1111 => (((*self.0 as u16) << 2) | 0b01).encode_to(dest),
@@ -308,6 +675,9 @@ impl<'a> Encode for CompactRef<'a, u32> {
 				self.0.encode_to(dest);
 			}
 		}
+
+		#[cfg(debug_assertions)]
+		dest.check(Compact::compact_len(self.0));
 	}
 
 	fn using_encoded<R, F: FnOnce(&[u8]) -> R>(&self, f: F) -> R {
@@ -334,6 +704,9 @@ impl<'a> Encode for CompactRef<'a, u64> {
 	}
 
 	fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+		#[cfg(debug_assertions)]
+		let dest = &mut MinimalModeCheck::new(dest);
+
 		match self.0 {
 			0..=0b0011_1111 => dest.push_byte((*self.0 as u8) << 2),
 			0..=0b0011_1111_1111_1111 => (((*self.0 as u16) << 2) | 0b01).encode_to(dest),
@@ -350,6 +723,9 @@ impl<'a> Encode for CompactRef<'a, u64> {
 				assert_eq!(v, 0, "shifted sufficient bits right to lead only leading zeros; qed")
 			}
 		}
+
+		#[cfg(debug_assertions)]
+		dest.check(Compact::compact_len(self.0));
 	}
 
 	fn using_encoded<R, F: FnOnce(&[u8]) -> R>(&self, f: F) -> R {
@@ -359,16 +735,30 @@ impl<'a> Encode for CompactRef<'a, u64> {
 	}
 }
 
+/// Returns the number of bytes of the compact encoding of `value`.
+///
+/// This is a `const fn`, so it can be used to compute bounds at compile time.
+///
+/// ```
+/// # use parity_scale_codec::compact_len;
+/// const LEN: usize = compact_len(1 << 14);
+/// assert_eq!(LEN, 4);
+/// ```
+pub const fn compact_len(value: u64) -> usize {
+	if value <= 0b0011_1111 {
+		1
+	} else if value <= 0b0011_1111_1111_1111 {
+		2
+	} else if value <= 0b0011_1111_1111_1111_1111_1111_1111_1111 {
+		4
+	} else {
+		(8 - value.leading_zeros() / 8) as usize + 1
+	}
+}
+
 impl CompactLen<u64> for Compact<u64> {
 	fn compact_len(val: &u64) -> usize {
-		match val {
-			0..=0b0011_1111 => 1,
-			0..=0b0011_1111_1111_1111 => 2,
-			0..=0b0011_1111_1111_1111_1111_1111_1111_1111 => 4,
-			_ => {
-				(8 - val.leading_zeros() / 8) as usize + 1
-			},
-		}
+		compact_len(*val)
 	}
 }
 
@@ -378,6 +768,9 @@ impl<'a> Encode for CompactRef<'a, u128> {
 	}
 
 	fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+		#[cfg(debug_assertions)]
+		let dest = &mut MinimalModeCheck::new(dest);
+
 		match self.0 {
 			0..=0b0011_1111 => dest.push_byte((*self.0 as u8) << 2),
 			0..=0b0011_1111_1111_1111 => (((*self.0 as u16) << 2) | 0b01).encode_to(dest),
@@ -394,6 +787,9 @@ impl<'a> Encode for CompactRef<'a, u128> {
 				assert_eq!(v, 0, "shifted sufficient bits right to lead only leading zeros; qed")
 			}
 		}
+
+		#[cfg(debug_assertions)]
+		dest.check(Compact::compact_len(self.0));
 	}
 
 	fn using_encoded<R, F: FnOnce(&[u8]) -> R>(&self, f: F) -> R {
@@ -786,6 +1182,144 @@ mod tests {
 		}
 	}
 
+	#[test]
+	fn compact_seconds_encoding_works() {
+		use core::time::Duration;
+
+		for &(secs, len) in &[(1u64, 1usize), (3600, 2), (u64::max_value(), 9)] {
+			let seconds = Seconds::from(Duration::from_secs(secs));
+			let encoded = Compact(seconds).encode();
+			assert_eq!(encoded, Compact(secs).encode());
+			assert_eq!(encoded.len(), len);
+
+			let decoded = <Compact<Seconds>>::decode(&mut &encoded[..]).unwrap().0;
+			assert_eq!(decoded, seconds);
+			assert_eq!(Duration::from(decoded), Duration::from_secs(secs));
+		}
+
+		// The sub-second part is dropped.
+		let seconds = Seconds::from(Duration::new(3600, 999_999_999));
+		assert_eq!(seconds.as_secs(), 3600);
+		assert_eq!(Compact(seconds).encode(), Compact(3600u64).encode());
+	}
+
+	#[test]
+	fn compact_duration_roundtrip() {
+		use core::time::Duration;
+
+		for &(duration, len) in &[
+			(Duration::from_nanos(1), 1usize),
+			(Duration::from_nanos(63), 1),
+			(Duration::from_millis(1), 4),
+			(Duration::from_millis(1500), 5),
+			(Duration::from_secs(3600), 7),
+			(Duration::new(u64::max_value(), 999_999_999), 13),
+		] {
+			let encoded = CompactDuration(duration).encode();
+			assert_eq!(encoded, Compact(duration.as_nanos()).encode());
+			assert_eq!(encoded.len(), len);
+			assert_eq!(encoded.len(), CompactDuration(duration).size_hint());
+			assert_eq!(CompactDuration::decode(&mut &encoded[..]), Ok(CompactDuration(duration)));
+		}
+	}
+
+	#[test]
+	fn delta_zig_zag_sequence() {
+		let deltas = [0i32, -1, 1, -2, 2, 63, -64, 64, -65, i32::max_value(), i32::min_value()];
+		let zig_zags = [0u32, 1, 2, 3, 4, 126, 127, 128, 129, u32::max_value() - 1, u32::max_value()];
+
+		for (delta, zig_zag) in deltas.iter().zip(zig_zags.iter()) {
+			let encoded = Delta(*delta).encode();
+			assert_eq!(encoded, Compact(*zig_zag).encode(), "zig-zag of {}", delta);
+			assert_eq!(encoded.len(), Delta(*delta).size_hint());
+			assert_eq!(Delta::<i32>::decode(&mut &encoded[..]), Ok(Delta(*delta)));
+		}
+
+		// Small deltas of both signs take a single byte.
+		let series: Vec<Delta<i64>> = [3i64, -2, 0, 17, -31, 31].iter().copied().map(Delta).collect();
+		let encoded = series.encode();
+		assert_eq!(encoded.len(), 1 + series.len());
+		assert_eq!(<Vec<Delta<i64>>>::decode(&mut &encoded[..]), Ok(series));
+	}
+
+	#[test]
+	fn delta_extremes_of_all_widths() {
+		assert_eq!(Delta(i8::min_value()).encode(), Compact(u8::max_value()).encode());
+		let max = Compact(u8::max_value() - 1).encode();
+		assert_eq!(Delta::<i8>::decode(&mut &max[..]), Ok(Delta(i8::max_value())));
+		assert_eq!(Delta::<i16>::decode(&mut &Delta(-300i16).encode()[..]), Ok(Delta(-300)));
+		let min = Delta(i128::min_value()).encode();
+		assert_eq!(Delta::<i128>::decode(&mut &min[..]), Ok(Delta(i128::min_value())));
+		assert!(Delta::<i8>::decode(&mut &Compact(256u16).encode()[..]).is_err());
+	}
+
+	#[test]
+	#[cfg(feature = "max-encoded-len")]
+	fn delta_max_encoded_len() {
+		use crate::MaxEncodedLen;
+
+		assert_eq!(Delta::<i64>::max_encoded_len(), Delta(i64::min_value()).encode().len());
+		assert_eq!(Delta::<i8>::max_encoded_len(), 2);
+	}
+
+	#[test]
+	fn compact_fixed_forces_four_bytes_mode() {
+		for &value in &[0u32, 1, 63, 64, (1 << 14) - 1, 1 << 14, (1 << 30) - 1] {
+			let fixed = CompactFixed::<u32, 2>::new(value).unwrap();
+			let encoded = fixed.encode();
+			assert_eq!(encoded, ((value << 2) | 0b10).to_le_bytes().to_vec());
+			assert_eq!(encoded.len(), fixed.size_hint());
+			assert_eq!(CompactFixed::<u32, 2>::decode(&mut &encoded[..]), Ok(fixed));
+		}
+		assert!(CompactFixed::<u32, 2>::new(1 << 30).is_err());
+
+		// The canonical encoding of a small value is another mode.
+		assert!(CompactFixed::<u32, 2>::decode(&mut &Compact(1u32).encode()[..]).is_err());
+		// And the forced mode is not canonical.
+		let forced = CompactFixed::<u64, 2>::new(1).unwrap().encode();
+		assert!(Compact::<u64>::decode(&mut &forced[..]).is_err());
+	}
+
+	#[test]
+	fn compact_fixed_other_modes() {
+		assert_eq!(CompactFixed::<u8, 0>::new(63).unwrap().encode(), Compact(63u8).encode());
+		assert!(CompactFixed::<u8, 0>::new(64).is_err());
+		assert_eq!(CompactFixed::<u16, 1>::new(1).unwrap().encode(), vec![0b0000_0101, 0]);
+		assert!(CompactFixed::<u64, 4>::new(0).is_err());
+
+		for &value in &[0u64, 1, 1 << 32, u64::max_value()] {
+			let fixed = CompactFixed::<u64, 3>::new(value).unwrap();
+			let encoded = fixed.encode();
+			if value >= 1 << 30 {
+				assert_eq!(encoded, Compact(value).encode());
+			}
+			assert_eq!(encoded.len(), fixed.size_hint());
+			assert_eq!(CompactFixed::<u64, 3>::decode(&mut &encoded[..]), Ok(fixed));
+		}
+		assert_eq!(CompactFixed::<u64, 3>::new(1).unwrap().encode(), vec![0b11, 1, 0, 0, 0]);
+
+		let too_large = CompactFixed::<u64, 3>::new(1 << 40).unwrap().encode();
+		assert!(CompactFixed::<u32, 3>::decode(&mut &too_large[..]).is_err());
+		assert!(CompactFixed::<u64, 3>::decode(&mut &[0b0111, 1, 0, 0, 0, 0][..]).is_err());
+	}
+
+	#[test]
+	#[cfg(feature = "max-encoded-len")]
+	fn compact_fixed_max_encoded_len() {
+		use crate::MaxEncodedLen;
+
+		assert_eq!(CompactFixed::<u32, 2>::max_encoded_len(), 4);
+		assert_eq!(CompactFixed::<u16, 3>::max_encoded_len(), 5);
+		assert_eq!(CompactFixed::<u128, 3>::max_encoded_len(), 17);
+	}
+
+	#[test]
+	fn compact_duration_out_of_range() {
+		let max = core::time::Duration::new(u64::max_value(), 999_999_999).as_nanos();
+		assert!(CompactDuration::decode(&mut &Compact(max + 1).encode()[..]).is_err());
+		assert!(CompactDuration::decode(&mut &Compact(u128::max_value()).encode()[..]).is_err());
+	}
+
 	struct WithCompact<T: HasCompact> {
 		_data: T,
 	}
@@ -810,6 +1344,44 @@ mod tests {
 		CompactRef(&std::u128::MAX).using_encoded(|_| {});
 	}
 
+	#[test]
+	#[cfg(debug_assertions)]
+	fn minimal_mode_check_accepts_all_modes() {
+		let tests = [
+			0u128, 63, 64, 16383, 16384, 1073741823, 1073741824, (1 << 32) - 1, 1 << 32,
+			(1 << 56) - 1, 1 << 56, u64::max_value() as u128, 1 << 64, u128::max_value(),
+		];
+		for &n in &tests {
+			let mut dest = Vec::new();
+			Compact(n).encode_to(&mut dest);
+			assert_eq!(dest.len(), Compact::compact_len(&n));
+
+			if n <= u64::max_value() as u128 {
+				Compact(n as u64).encode_to(&mut Vec::new());
+			}
+			if n <= u32::max_value() as u128 {
+				Compact(n as u32).encode_to(&mut Vec::new());
+			}
+			if n <= u16::max_value() as u128 {
+				Compact(n as u16).encode_to(&mut Vec::new());
+			}
+			if n <= u8::max_value() as u128 {
+				Compact(n as u8).encode_to(&mut Vec::new());
+			}
+		}
+	}
+
+	#[test]
+	#[cfg(debug_assertions)]
+	#[should_panic(expected = "Compact value was not encoded in its minimal mode")]
+	fn minimal_mode_check_rejects_non_minimal() {
+		let mut dest = Vec::new();
+		let mut check = MinimalModeCheck::new(&mut dest);
+		// `1` in the four-byte mode.
+		((1u32 << 2) | 0b10).encode_to(&mut check);
+		check.check(Compact::compact_len(&1u32));
+	}
+
 	#[test]
 	#[should_panic]
 	fn array_vec_output_oob() {
@@ -915,6 +1487,37 @@ mod tests {
 		}
 	}
 
+	#[test]
+	fn compact_len_at_mode_boundaries() {
+		let tests = [
+			(0u64, 1usize), ((1 << 6) - 1, 1), (1 << 6, 2), ((1 << 14) - 1, 2), (1 << 14, 4),
+			((1 << 30) - 1, 4), (1 << 30, 5), ((1 << 32) - 1, 5), (1 << 32, 6), ((1 << 56) - 1, 8),
+			(1 << 56, 9), (u64::max_value(), 9),
+		];
+		for &(n, l) in &tests {
+			assert_eq!(super::compact_len(n), l);
+			assert_eq!(Compact(n).encode().len(), l);
+		}
+
+		const BOUND: usize = super::compact_len(u32::max_value() as u64);
+		assert_eq!(BOUND, 5);
+	}
+
+	#[test]
+	fn non_minimal_encoding_of_one_is_rejected() {
+		// `1` in the two-byte, four-byte and big-integer modes.
+		let non_minimal: [&[u8]; 3] = [&[0b0101, 0], &[0b0110, 0, 0, 0], &[0b11, 1, 0, 0, 0]];
+		for encoded in non_minimal.iter() {
+			assert!(<Compact<u8>>::decode(&mut &encoded[..]).is_err());
+			assert!(<Compact<u16>>::decode(&mut &encoded[..]).is_err());
+			assert!(<Compact<u32>>::decode(&mut &encoded[..]).is_err());
+			assert!(<Compact<u64>>::decode(&mut &encoded[..]).is_err());
+			assert!(<Compact<u128>>::decode(&mut &encoded[..]).is_err());
+		}
+
+		assert_eq!(<Compact<u32>>::decode(&mut &[0b0100][..]), Ok(Compact(1)));
+	}
+
 	macro_rules! quick_check_roundtrip {
 		( $( $ty:ty : $test:ident ),* ) => {
 			$(
@@ -0,0 +1,91 @@
+// Copyright 2021 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{Error, Input};
+
+/// The `log` target used by [`DebugInput`].
+pub const DEBUG_INPUT_LOG_TARGET: &str = "parity-scale-codec::debug-input";
+
+/// Input that emits a `trace` level `log` record for every read of the wrapped input.
+///
+/// Each record contains the offset of the read and the bytes read, in the form
+/// `read 4 bytes at offset 0: [05, 00, 00, 00]`. Primitives are decoded with a single read, so
+/// the records follow the fields of the decoded value. Entering and leaving nested references
+/// (see [`Input::descend_ref`]) is logged as well.
+///
+/// This is meant for inspecting unknown payloads during development and should not be used in
+/// production, as formatting every read is slow.
+pub struct DebugInput<I> {
+	input: I,
+	offset: usize,
+}
+
+impl<I: Input> DebugInput<I> {
+	/// Create a new instance wrapping the given input.
+	pub fn new(input: I) -> Self {
+		Self { input, offset: 0 }
+	}
+
+	/// The number of bytes read so far.
+	pub fn offset(&self) -> usize {
+		self.offset
+	}
+
+	/// Return the wrapped input.
+	pub fn into_inner(self) -> I {
+		self.input
+	}
+}
+
+impl<I: Input> Input for DebugInput<I> {
+	fn remaining_len(&mut self) -> Result<Option<usize>, Error> {
+		self.input.remaining_len()
+	}
+
+	fn read(&mut self, into: &mut [u8]) -> Result<(), Error> {
+		match self.input.read(into) {
+			Ok(()) => {
+				log::trace!(
+					target: DEBUG_INPUT_LOG_TARGET,
+					"read {} bytes at offset {}: {:02x?}",
+					into.len(),
+					self.offset,
+					into,
+				);
+				self.offset += into.len();
+				Ok(())
+			},
+			Err(e) => {
+				log::trace!(
+					target: DEBUG_INPUT_LOG_TARGET,
+					"failed to read {} bytes at offset {}: {}",
+					into.len(),
+					self.offset,
+					e,
+				);
+				Err(e)
+			},
+		}
+	}
+
+	fn descend_ref(&mut self) -> Result<(), Error> {
+		log::trace!(target: DEBUG_INPUT_LOG_TARGET, "descend at offset {}", self.offset);
+		self.input.descend_ref()
+	}
+
+	fn ascend_ref(&mut self) {
+		log::trace!(target: DEBUG_INPUT_LOG_TARGET, "ascend at offset {}", self.offset);
+		self.input.ascend_ref()
+	}
+}
@@ -0,0 +1,123 @@
+// Copyright 2021 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::alloc::borrow::Cow;
+use crate::{Compact, Decode, Error};
+
+/// Trait for types which can borrow their data from a byte slice input instead of copying it.
+///
+/// This is implemented for `Cow<'a, [u8]>` and `Cow<'a, str>`, which decode as
+/// `Cow::Borrowed` pointing into the input. They are encoded like `Vec<u8>` and `String`.
+/// Decoding them with [`Decode`] from any other input returns a `Cow::Owned` copy instead.
+///
+/// ```
+/// # use std::borrow::Cow;
+/// # use parity_scale_codec::{Encode, DecodeCow};
+/// let encoded = "hello".encode();
+/// let decoded = <Cow<str>>::decode_cow(&mut &encoded[..]).unwrap();
+/// assert!(matches!(decoded, Cow::Borrowed("hello")));
+/// ```
+pub trait DecodeCow<'a>: Sized {
+	/// Attempt to decode the value from `input`, borrowing from it where possible.
+	fn decode_cow(input: &mut &'a [u8]) -> Result<Self, Error>;
+}
+
+/// Split off the data of a length prefixed byte sequence from `input`.
+///
+/// `input` is left untouched on error.
+fn split_bytes<'a>(input: &mut &'a [u8]) -> Result<&'a [u8], Error> {
+	let mut attempt = *input;
+	let len = <Compact<u32>>::decode(&mut attempt)?.0 as usize;
+	if len > attempt.len() {
+		return Err("Not enough data to fill buffer".into());
+	}
+
+	let (data, rest) = attempt.split_at(len);
+	*input = rest;
+	Ok(data)
+}
+
+impl<'a> DecodeCow<'a> for Cow<'a, [u8]> {
+	fn decode_cow(input: &mut &'a [u8]) -> Result<Self, Error> {
+		split_bytes(input).map(Cow::Borrowed)
+	}
+}
+
+impl<'a> DecodeCow<'a> for Cow<'a, str> {
+	fn decode_cow(input: &mut &'a [u8]) -> Result<Self, Error> {
+		let mut attempt = *input;
+		let data = core::str::from_utf8(split_bytes(&mut attempt)?)
+			.map_err(|_| Error::from("Invalid utf8 sequence"))?;
+		*input = attempt;
+		Ok(Cow::Borrowed(data))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::Encode;
+
+	#[test]
+	fn borrowed_bytes_share_memory_with_input() {
+		let encoded = (vec![1u8, 2, 3], 7u8).encode();
+		let mut input = &encoded[..];
+
+		let decoded = <Cow<[u8]>>::decode_cow(&mut input).unwrap();
+		match decoded {
+			Cow::Borrowed(bytes) => {
+				assert_eq!(bytes, &[1, 2, 3]);
+				assert_eq!(bytes.as_ptr(), encoded[1..].as_ptr());
+			},
+			Cow::Owned(_) => panic!("Bytes decoded from a slice must be borrowed"),
+		}
+		assert_eq!(input, &[7]);
+	}
+
+	#[test]
+	fn borrowed_str_shares_memory_with_input() {
+		let encoded = ("hello", "").encode();
+		let mut input = &encoded[..];
+
+		match <Cow<str>>::decode_cow(&mut input).unwrap() {
+			Cow::Borrowed(s) => {
+				assert_eq!(s, "hello");
+				assert_eq!(s.as_ptr(), encoded[1..].as_ptr());
+			},
+			Cow::Owned(_) => panic!("A str decoded from a slice must be borrowed"),
+		}
+		assert_eq!(<Cow<str>>::decode_cow(&mut input), Ok(Cow::Borrowed("")));
+		assert!(input.is_empty());
+	}
+
+	#[test]
+	fn owned_inputs_decode_owned() {
+		let encoded = "hello".encode();
+		let decoded = <Cow<str>>::decode(&mut crate::IoReader(&encoded[..])).unwrap();
+		assert!(matches!(decoded, Cow::Owned(ref s) if s == "hello"));
+	}
+
+	#[test]
+	fn invalid_input_is_an_error() {
+		let truncated = [3 << 2, 1, 2];
+		let mut input = &truncated[..];
+		assert!(<Cow<[u8]>>::decode_cow(&mut input).is_err());
+		assert_eq!(input, &truncated[..]);
+
+		let encoded = vec![0xffu8, 0xfe].encode();
+		let mut input = &encoded[..];
+		assert!(<Cow<str>>::decode_cow(&mut input).is_err());
+		assert_eq!(input, &encoded[..]);
+	}
+}
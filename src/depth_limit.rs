@@ -18,6 +18,11 @@ use crate::{Error, Decode, Input};
 const DECODE_MAX_DEPTH_MSG: &str = "Maximum recursion depth reached when decoding";
 
 /// Extension trait to [`Decode`] for decoding with a maximum recursion depth.
+///
+/// The depth is increased by every value decoded through [`Input::descend_ref`]: boxed values
+/// and collections of non primitive elements. Nested collections like `Vec<Vec<Vec<u32>>>` are
+/// thus limited as well as recursive types, e.g. `Vec<Vec<u8>>` has a depth of 1 as the bytes are
+/// read at once.
 pub trait DecodeLimit: Sized {
 	/// Decode `Self` with the given maximum recursion depth.
 	///
@@ -50,6 +55,11 @@ impl<'a, I:Input> Input for DepthTrackingInput<'a, I> {
 		self.input.read_byte()
 	}
 
+	#[cfg(feature = "bytes")]
+	fn scale_internal_decode_bytes(&mut self) -> Result<bytes::Bytes, Error> {
+		self.input.scale_internal_decode_bytes()
+	}
+
 	fn descend_ref(&mut self) -> Result<(), Error> {
 		self.input.descend_ref()?;
 		self.depth += 1;
@@ -107,4 +117,36 @@ mod tests {
 		assert_eq!(decoded, nested);
 		assert!(NestedVec::decode_with_depth_limit(2, &encoded).is_err());
 	}
+
+	#[test]
+	fn decode_limit_counts_every_collection() {
+		use crate::alloc::collections::{BTreeMap, BTreeSet, LinkedList, VecDeque};
+
+		let nested = vec![VecDeque::from(vec![vec![Some(1u32)]])];
+		let encoded = nested.encode();
+		assert_eq!(<Vec<VecDeque<Vec<Option<u32>>>>>::decode_with_depth_limit(3, &encoded), Ok(nested));
+		assert!(<Vec<VecDeque<Vec<Option<u32>>>>>::decode_with_depth_limit(2, &encoded).is_err());
+
+		let mut inner = BTreeMap::new();
+		inner.insert(1u8, vec![vec![(1u16, 2u16)].into_iter().collect::<BTreeSet<_>>()]);
+		let nested: LinkedList<_> = vec![inner].into_iter().collect();
+		let encoded = nested.encode();
+		type Nested = LinkedList<BTreeMap<u8, Vec<BTreeSet<(u16, u16)>>>>;
+		assert_eq!(Nested::decode_with_depth_limit(4, &encoded), Ok(nested));
+		assert!(Nested::decode_with_depth_limit(3, &encoded).is_err());
+
+		// Empty collections don't need their elements' depth.
+		let empty: Vec<Vec<Vec<u32>>> = vec![vec![]];
+		assert_eq!(<Vec<Vec<Vec<u32>>>>::decode_with_depth_limit(2, &empty.encode()), Ok(empty));
+	}
+
+	#[test]
+	fn decode_limit_rejects_deeply_nested_vecs() {
+		// Eleven nested `Vec`s in eleven bytes.
+		type Deep = Vec<Vec<Vec<Vec<Vec<Vec<Vec<Vec<Vec<Vec<Vec<u32>>>>>>>>>>>;
+		let encoded = [4u8; 10].iter().chain(&[0]).copied().collect::<Vec<_>>();
+		assert!(Deep::decode(&mut &encoded[..]).is_ok());
+		assert!(Deep::decode_all_with_depth_limit(10, &encoded).is_ok());
+		assert!(Deep::decode_all_with_depth_limit(9, &encoded).is_err());
+	}
 }
@@ -160,4 +160,37 @@ mod tests {
 			ComplexStuff::<(u32, Vec<u8>)>::complex_method(&(1u32, slice))
 		);
 	}
+
+	#[test]
+	fn integer_references_encode_like_integers() {
+		fn encode_like<T: Encode, R: EncodeLike<T>>(value: R) -> Vec<u8> {
+			value.encode()
+		}
+
+		assert_eq!((&5u32).encode(), 5u32.encode());
+		assert_eq!(encode_like::<u32, _>(&5u32), 5u32.encode());
+		assert_eq!(encode_like::<u8, _>(&mut 5u8), 5u8.encode());
+		assert_eq!(encode_like::<i128, _>(&&-5i128), (-5i128).encode());
+
+		let values = vec![1u64, 2, 3];
+		let refs: Vec<&u64> = values.iter().collect();
+		assert_eq!(encode_like::<Vec<u64>, _>(&refs[..]), values.encode());
+	}
+
+	#[test]
+	fn btreemap_with_borrowed_keys_encodes_like_owned_keys() {
+		fn encode_like<T: Encode, R: EncodeLike<T>>(value: &R) -> Vec<u8> {
+			value.encode()
+		}
+
+		let owned: BTreeMap<String, Vec<u8>> = vec![
+			("b".to_string(), vec![2]),
+			("a".to_string(), vec![1, 1]),
+			("ab".to_string(), vec![]),
+		].into_iter().collect();
+		let borrowed: BTreeMap<&str, &[u8]> = owned.iter().map(|(k, v)| (&k[..], &v[..])).collect();
+
+		assert_eq!(encode_like::<BTreeMap<String, Vec<u8>>, _>(&borrowed), owned.encode());
+		assert_eq!(<BTreeMap<String, Vec<u8>> as crate::Decode>::decode(&mut &borrowed.encode()[..]), Ok(owned));
+	}
 }
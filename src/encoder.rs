@@ -0,0 +1,108 @@
+// Copyright 2021 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::alloc::vec::Vec;
+use crate::{Encode, Output};
+
+/// Buffer to encode several values one after the other.
+///
+/// Each appended value reserves its [`Encode::size_hint`] before being encoded.
+///
+/// ```
+/// # use parity_scale_codec::{Encode, Encoder};
+/// let message = Encoder::new().append(&1u8).append(&vec![2u16]).append("three").into_inner();
+/// assert_eq!(message, (1u8, vec![2u16], "three").encode());
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Encoder {
+	buffer: Vec<u8>,
+}
+
+impl Encoder {
+	/// Create an empty encoder.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Create an empty encoder which can hold at least `capacity` bytes without reallocating.
+	pub fn with_capacity(capacity: usize) -> Self {
+		Self { buffer: Vec::with_capacity(capacity) }
+	}
+
+	/// Append the encoding of `value`.
+	pub fn append<T: Encode + ?Sized>(mut self, value: &T) -> Self {
+		self.buffer.reserve(value.size_hint());
+		value.encode_to(&mut self.buffer);
+		self
+	}
+
+	/// The bytes encoded so far.
+	pub fn as_bytes(&self) -> &[u8] {
+		&self.buffer
+	}
+
+	/// Consume the encoder and return the encoded bytes.
+	pub fn into_inner(self) -> Vec<u8> {
+		self.buffer
+	}
+}
+
+impl Output for Encoder {
+	fn write(&mut self, bytes: &[u8]) {
+		self.buffer.extend_from_slice(bytes)
+	}
+
+	fn push_byte(&mut self, byte: u8) {
+		self.buffer.push(byte)
+	}
+
+	fn write_byte_repeated(&mut self, byte: u8, count: usize) {
+		self.buffer.resize(self.buffer.len() + count, byte)
+	}
+}
+
+impl From<Encoder> for Vec<u8> {
+	fn from(encoder: Encoder) -> Self {
+		encoder.into_inner()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::Compact;
+
+	#[test]
+	fn encoder_matches_manual_concatenation() {
+		let a = 0x0102_0304u32;
+		let b = Compact(1u64 << 40);
+		let c = vec![Some(1u16), None];
+
+		let mut expected = a.encode();
+		expected.extend(b.encode());
+		expected.extend(c.encode());
+
+		let encoder = Encoder::with_capacity(16).append(&a).append(&b).append(&c);
+		assert_eq!(encoder.as_bytes(), &expected[..]);
+		assert_eq!(Vec::from(encoder), expected);
+	}
+
+	#[test]
+	fn encoder_is_an_output() {
+		let mut encoder = Encoder::new();
+		7u16.encode_to(&mut encoder);
+		encoder.push_byte(8);
+		assert_eq!(encoder.append(&9u8).into_inner(), vec![7, 0, 8, 9]);
+	}
+}
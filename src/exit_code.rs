@@ -0,0 +1,135 @@
+// Copyright 2021 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{Decode, Encode, EncodeLike, Error, Input, Output};
+
+/// A portable process exit code, encoded like an `i32`.
+///
+/// `std::process::ExitStatus` is opaque and platform specific, and `std::process::ExitCode` has
+/// no accessor for its value, so neither can be encoded directly. An `ExitStatus` of a process
+/// which exited normally can be converted into an `ExitCode` with `TryFrom`, which fails if the
+/// process was terminated by a signal instead.
+///
+/// ```
+/// # use parity_scale_codec::{Encode, Decode, ExitCode};
+/// let code = ExitCode(2);
+/// assert!(!code.is_success());
+/// assert_eq!(code.encode(), 2i32.encode());
+/// assert_eq!(ExitCode::decode(&mut &code.encode()[..]), Ok(code));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ExitCode(pub i32);
+
+impl ExitCode {
+	/// The exit code of a successful process.
+	pub const SUCCESS: ExitCode = ExitCode(0);
+
+	/// The conventional exit code of a failed process.
+	pub const FAILURE: ExitCode = ExitCode(1);
+
+	/// Whether this is the exit code of a successful process.
+	pub fn is_success(&self) -> bool {
+		*self == Self::SUCCESS
+	}
+}
+
+impl From<i32> for ExitCode {
+	fn from(code: i32) -> Self {
+		ExitCode(code)
+	}
+}
+
+impl From<ExitCode> for i32 {
+	fn from(code: ExitCode) -> Self {
+		code.0
+	}
+}
+
+#[cfg(feature = "std")]
+impl core::convert::TryFrom<std::process::ExitStatus> for ExitCode {
+	type Error = Error;
+
+	fn try_from(status: std::process::ExitStatus) -> Result<Self, Error> {
+		status.code().map(ExitCode).ok_or_else(|| "Process was terminated by a signal".into())
+	}
+}
+
+impl Encode for ExitCode {
+	fn size_hint(&self) -> usize {
+		self.0.size_hint()
+	}
+
+	fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+		self.0.encode_to(dest)
+	}
+
+	fn using_encoded<R, F: FnOnce(&[u8]) -> R>(&self, f: F) -> R {
+		self.0.using_encoded(f)
+	}
+}
+
+impl EncodeLike for ExitCode {}
+
+impl Decode for ExitCode {
+	fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+		i32::decode(input).map(ExitCode)
+	}
+}
+
+#[cfg(feature = "max-encoded-len")]
+impl crate::MaxEncodedLen for ExitCode {
+	fn max_encoded_len() -> usize {
+		4
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn exit_code_roundtrip() {
+		let codes = [ExitCode::SUCCESS, ExitCode::FAILURE, ExitCode(-1), ExitCode(255), ExitCode(i32::max_value())];
+		for &code in &codes {
+			let encoded = code.encode();
+			assert_eq!(encoded, code.0.encode());
+			assert_eq!(ExitCode::decode(&mut &encoded[..]), Ok(code));
+		}
+
+		assert!(ExitCode::SUCCESS.is_success());
+		assert!(!ExitCode::FAILURE.is_success());
+		assert!(ExitCode::decode(&mut &[0, 0, 0][..]).is_err());
+	}
+
+	#[test]
+	#[cfg(unix)]
+	fn exit_code_from_exit_status() {
+		use core::convert::TryFrom;
+		use std::os::unix::process::ExitStatusExt;
+		use std::process::ExitStatus;
+
+		assert_eq!(ExitCode::try_from(ExitStatus::from_raw(0)), Ok(ExitCode::SUCCESS));
+		assert_eq!(ExitCode::try_from(ExitStatus::from_raw(3 << 8)), Ok(ExitCode(3)));
+		// Terminated by `SIGKILL`.
+		assert!(ExitCode::try_from(ExitStatus::from_raw(9)).is_err());
+	}
+
+	#[test]
+	#[cfg(feature = "max-encoded-len")]
+	fn exit_code_max_encoded_len() {
+		use crate::MaxEncodedLen;
+
+		assert_eq!(ExitCode::max_encoded_len(), ExitCode(i32::min_value()).encode().len());
+	}
+}
@@ -0,0 +1,116 @@
+// Copyright 2021 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! `fixed` point number specific serialization.
+//!
+//! A fixed point number is encoded like the integer holding its bits, e.g. an `I32F32` like the
+//! `i64` returned by `to_bits`. The number of fractional bits is part of the type, not of the
+//! encoding.
+
+use core::mem;
+
+use fixed::{
+	FixedI8, FixedI16, FixedI32, FixedI64, FixedI128, FixedU8, FixedU16, FixedU32, FixedU64, FixedU128,
+};
+use fixed::types::extra::{LeEqU8, LeEqU16, LeEqU32, LeEqU64, LeEqU128};
+
+use crate::{Encode, Decode, Input, Output, Error};
+use crate::encode_like::EncodeLike;
+
+macro_rules! impl_for_fixed {
+	( $( $type:ident<$frac:ident> as $bits:ty, )* ) => { $(
+		impl<Frac: $frac> Encode for $type<Frac> {
+			fn size_hint(&self) -> usize {
+				mem::size_of::<$bits>()
+			}
+
+			fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+				self.to_bits().encode_to(dest)
+			}
+
+			fn using_encoded<R, F: FnOnce(&[u8]) -> R>(&self, f: F) -> R {
+				self.to_bits().using_encoded(f)
+			}
+		}
+
+		impl<Frac: $frac> EncodeLike for $type<Frac> {}
+
+		impl<Frac: $frac> Decode for $type<Frac> {
+			fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+				<$bits>::decode(input).map(Self::from_bits)
+			}
+
+			fn encoded_fixed_size() -> Option<usize> {
+				Some(mem::size_of::<$bits>())
+			}
+		}
+
+		#[cfg(feature = "max-encoded-len")]
+		impl<Frac: $frac> crate::MaxEncodedLen for $type<Frac> {
+			fn max_encoded_len() -> usize {
+				mem::size_of::<$bits>()
+			}
+		}
+	)* }
+}
+
+impl_for_fixed! {
+	FixedI8<LeEqU8> as i8,
+	FixedI16<LeEqU16> as i16,
+	FixedI32<LeEqU32> as i32,
+	FixedI64<LeEqU64> as i64,
+	FixedI128<LeEqU128> as i128,
+	FixedU8<LeEqU8> as u8,
+	FixedU16<LeEqU16> as u16,
+	FixedU32<LeEqU32> as u32,
+	FixedU64<LeEqU64> as u64,
+	FixedU128<LeEqU128> as u128,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use fixed::types::{I32F32, I8F8, U64F64};
+
+	#[test]
+	fn fixed_roundtrip() {
+		let value = I32F32::from_num(-2.5);
+		let encoded = value.encode();
+		assert_eq!(encoded, value.to_bits().encode());
+		assert_eq!(encoded, (-5i64 << 31).encode());
+		assert_eq!(encoded.len(), value.size_hint());
+		assert_eq!(I32F32::decode(&mut &encoded[..]), Ok(value));
+
+		let value = U64F64::from_num(1) / 3;
+		let encoded = value.encode();
+		assert_eq!(encoded.len(), 16);
+		assert_eq!(U64F64::decode(&mut &encoded[..]), Ok(value));
+
+		assert_eq!(I8F8::MIN.encode(), i16::min_value().encode());
+		assert_eq!(I8F8::decode(&mut &I8F8::MIN.encode()[..]), Ok(I8F8::MIN));
+		assert_eq!(I8F8::decode(&mut &I8F8::MAX.encode()[..]), Ok(I8F8::MAX));
+
+		assert!(I32F32::decode(&mut &[0u8; 7][..]).is_err());
+	}
+
+	#[test]
+	#[cfg(feature = "max-encoded-len")]
+	fn fixed_max_encoded_len() {
+		use crate::MaxEncodedLen;
+
+		assert_eq!(I8F8::max_encoded_len(), 2);
+		assert_eq!(I32F32::max_encoded_len(), 8);
+		assert_eq!(U64F64::max_encoded_len(), 16);
+	}
+}
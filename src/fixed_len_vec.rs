@@ -0,0 +1,98 @@
+// Copyright 2021 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use core::mem;
+
+use crate::alloc::vec::Vec;
+use crate::codec::{decode_vec_with_len, encode_slice_no_len};
+use crate::encode_like::EncodeLike;
+use crate::{Decode, Encode, Error, Input, Output};
+
+/// A `Vec<T>` whose length prefix is a little endian `u32` instead of a `Compact<u32>`.
+///
+/// **This is not canonical SCALE.** It is meant to interoperate with formats using a fixed width
+/// length prefix. The elements are encoded like in a `Vec<T>`.
+///
+/// ```
+/// # use parity_scale_codec::{Encode, Decode, FixedLenVec};
+/// let v = FixedLenVec(vec![1u16, 2]);
+/// assert_eq!(v.encode(), vec![2, 0, 0, 0, 1, 0, 2, 0]);
+/// assert_eq!(FixedLenVec::decode(&mut &[1, 0, 0, 0, 7, 0][..]), Ok(FixedLenVec(vec![7u16])));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct FixedLenVec<T>(pub Vec<T>);
+
+impl<T> From<Vec<T>> for FixedLenVec<T> {
+	fn from(v: Vec<T>) -> Self {
+		Self(v)
+	}
+}
+
+impl<T> From<FixedLenVec<T>> for Vec<T> {
+	fn from(v: FixedLenVec<T>) -> Self {
+		v.0
+	}
+}
+
+impl<T: Encode> Encode for FixedLenVec<T> {
+	fn size_hint(&self) -> usize {
+		mem::size_of::<u32>() + mem::size_of::<T>() * self.0.len()
+	}
+
+	fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+		assert!(
+			self.0.len() <= u32::MAX as usize,
+			"Attempted to serialize a collection with too many elements.",
+		);
+		(self.0.len() as u32).encode_to(dest);
+		encode_slice_no_len(&self.0, dest)
+	}
+}
+
+impl<T: Encode> EncodeLike for FixedLenVec<T> {}
+
+impl<T: Decode> Decode for FixedLenVec<T> {
+	fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+		let len = u32::decode(input)?;
+		decode_vec_with_len(input, len as usize).map(Self)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn fixed_len_vec_layout() {
+		let values = vec![1u32, 2, 3];
+		let fixed = FixedLenVec(values.clone());
+		let encoded = fixed.encode();
+
+		// Same elements as a `Vec`, only the prefix differs.
+		let canonical = values.encode();
+		assert_eq!(&encoded[..4], &[3, 0, 0, 0]);
+		assert_eq!(canonical[0], 3 << 2);
+		assert_eq!(&encoded[4..], &canonical[1..]);
+
+		assert_eq!(FixedLenVec::decode(&mut &encoded[..]), Ok(fixed));
+		assert_eq!(FixedLenVec::<u8>::decode(&mut &[0, 0, 0, 0][..]), Ok(FixedLenVec(vec![])));
+	}
+
+	#[test]
+	fn fixed_len_vec_with_missing_elements() {
+		assert!(FixedLenVec::<u16>::decode(&mut &[2, 0, 0, 0, 1, 0][..]).is_err());
+		assert!(FixedLenVec::<u8>::decode(&mut &[0xff, 0xff, 0xff, 0xff][..]).is_err());
+		assert!(FixedLenVec::<u8>::decode(&mut &[1, 0, 0][..]).is_err());
+	}
+}
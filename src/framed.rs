@@ -0,0 +1,188 @@
+// Copyright 2021 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Length delimited framing of encoded messages.
+
+use crate::{Compact, Decode, DecodeAll, Encode, Error, Input, Output};
+use crate::codec::{compact_encode_len_to, decode_vec_with_len};
+
+/// Write `value` to `dest` as a frame: the compact length of its encoding, then the encoding.
+///
+/// This is the same layout as `value.encode().encode()`, without the intermediate allocation.
+/// Frames are read back with [`read_framed`].
+///
+/// # Panics
+///
+/// If the encoding of `value` is longer than `u32::MAX` bytes.
+///
+/// ```
+/// # use parity_scale_codec::{Encode, write_framed, read_framed};
+/// let mut stream = Vec::new();
+/// write_framed(&mut stream, &1u32);
+/// write_framed(&mut stream, &"hello");
+///
+/// let mut input = &stream[..];
+/// assert_eq!(read_framed::<u32, _>(&mut input), Ok(1));
+/// assert_eq!(read_framed::<String, _>(&mut input), Ok("hello".into()));
+/// assert!(input.is_empty());
+/// ```
+pub fn write_framed<W: Output + ?Sized, T: Encode + ?Sized>(dest: &mut W, value: &T) {
+	compact_encode_len_to(dest, value.encoded_size()).expect("Frame is too long");
+	value.encode_to(dest);
+}
+
+/// Read a frame written by [`write_framed`] from `input` and decode it as `T`.
+///
+/// The frame is read entirely before decoding. Decoding fails if `input` ends before the end of
+/// the frame, or if `T` doesn't consume all of the frame.
+pub fn read_framed<T: Decode, I: Input>(input: &mut I) -> Result<T, Error> {
+	let len = <Compact<u32>>::decode(input)
+		.map_err(|e| e.chain("Could not decode the frame length"))?.0 as usize;
+	// Read through `decode_vec_with_len`, so the preallocation is bounded by the input length.
+	let frame = decode_vec_with_len::<u8, _>(input, len)
+		.map_err(|e| e.chain("Could not read the frame"))?;
+	T::decode_all(&frame)
+}
+
+/// Read a compact length from `input`, then decode a `T` from exactly that many following bytes.
+///
+/// This reads the same layout as [`read_framed`], but decodes `T` directly from `input` instead of
+/// reading the frame first. Decoding fails if `T` reads past the end of the frame, or if it doesn't
+/// consume all of it.
+///
+/// ```
+/// # use parity_scale_codec::{Encode, decode_length_delimited};
+/// let encoded = (1u8, 2u16).encode().encode();
+/// assert_eq!(decode_length_delimited::<(u8, u16), _>(&mut &encoded[..]), Ok((1, 2)));
+/// assert!(decode_length_delimited::<u16, _>(&mut &encoded[..]).is_err());
+/// ```
+pub fn decode_length_delimited<T: Decode, I: Input>(input: &mut I) -> Result<T, Error> {
+	let len = <Compact<u32>>::decode(input)
+		.map_err(|e| e.chain("Could not decode the frame length"))?.0 as usize;
+	let mut delimited = DelimitedInput { input, remaining: len };
+	let value = T::decode(&mut delimited)?;
+	if delimited.remaining != 0 {
+		return Err("Input buffer has still data left after decoding the frame".into());
+	}
+	Ok(value)
+}
+
+/// Input reading at most `remaining` bytes from the wrapped input.
+struct DelimitedInput<'a, I> {
+	input: &'a mut I,
+	remaining: usize,
+}
+
+impl<'a, I: Input> DelimitedInput<'a, I> {
+	fn consume(&mut self, len: usize) -> Result<(), Error> {
+		if len > self.remaining {
+			return Err("Not enough data in the frame to decode the value".into());
+		}
+		self.remaining -= len;
+		Ok(())
+	}
+}
+
+impl<'a, I: Input> Input for DelimitedInput<'a, I> {
+	fn remaining_len(&mut self) -> Result<Option<usize>, Error> {
+		let remaining = self.remaining;
+		Ok(Some(self.input.remaining_len()?.map_or(remaining, |len| len.min(remaining))))
+	}
+
+	fn read(&mut self, into: &mut [u8]) -> Result<(), Error> {
+		self.consume(into.len())?;
+		self.input.read(into)
+	}
+
+	fn read_byte(&mut self) -> Result<u8, Error> {
+		self.consume(1)?;
+		self.input.read_byte()
+	}
+
+	fn descend_ref(&mut self) -> Result<(), Error> {
+		self.input.descend_ref()
+	}
+
+	fn ascend_ref(&mut self) {
+		self.input.ascend_ref()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn framed_messages_round_trip() {
+		let mut stream = Vec::new();
+		write_framed(&mut stream, &(1u8, vec![2u16, 3]));
+		write_framed(&mut stream, &Some(4u64));
+		let first = (1u8, vec![2u16, 3]).encode().encode();
+		assert_eq!(stream[..first.len()], first[..]);
+
+		let mut input = &stream[..];
+		assert_eq!(read_framed::<(u8, Vec<u16>), _>(&mut input), Ok((1, vec![2, 3])));
+		assert_eq!(read_framed::<Option<u64>, _>(&mut input), Ok(Some(4)));
+		assert!(input.is_empty());
+	}
+
+	#[test]
+	fn framed_message_must_match_its_length() {
+		// Trailing data in the frame.
+		let mut stream = Vec::new();
+		write_framed(&mut stream, &1u32);
+		assert!(read_framed::<u16, _>(&mut &stream[..]).is_err());
+
+		// Frame shorter than its length.
+		assert!(read_framed::<u32, _>(&mut &stream[..3]).is_err());
+		assert!(read_framed::<u32, _>(&mut &[4 << 2, 1, 0][..]).is_err());
+
+		// Frame shorter than the value.
+		assert!(read_framed::<u32, _>(&mut &[2 << 2, 1, 0][..]).is_err());
+	}
+
+	#[test]
+	fn length_delimited_value_must_fill_its_region() {
+		let mut stream = Vec::new();
+		write_framed(&mut stream, &(7u8, vec![1u32, 2]));
+		stream.push(9);
+		let mut input = &stream[..];
+		assert_eq!(decode_length_delimited::<(u8, Vec<u32>), _>(&mut input), Ok((7, vec![1, 2])));
+		assert_eq!(input, &[9]);
+
+		// Trailing bytes inside the delimited region.
+		let encoded = vec![(1u16, 2u8).encode(), vec![0xff]].concat().encode();
+		assert!(decode_length_delimited::<(u16, u8), _>(&mut &encoded[..]).is_err());
+
+		// The value reads past the delimited region, even if the input has more data.
+		let encoded = [&[2 << 2][..], &[1, 0, 0, 0][..]].concat();
+		assert!(decode_length_delimited::<u32, _>(&mut &encoded[..]).is_err());
+		assert!(decode_length_delimited::<u8, _>(&mut &encoded[..]).is_err());
+		assert_eq!(decode_length_delimited::<u16, _>(&mut &encoded[..]), Ok(1));
+
+		// Region longer than the input.
+		assert!(decode_length_delimited::<u16, _>(&mut &[4 << 2, 1, 0][..]).is_err());
+	}
+
+	#[test]
+	fn length_delimited_bounds_preallocation() {
+		// A `Vec` length larger than the region must not be trusted beyond the region.
+		let mut encoded = Compact(u32::max_value()).encode();
+		encoded.extend_from_slice(&[0; 8]);
+		let framed = encoded.encode();
+		let mut input = DelimitedInput { input: &mut &framed[1..], remaining: encoded.len() };
+		assert_eq!(input.remaining_len(), Ok(Some(encoded.len())));
+		assert!(decode_length_delimited::<Vec<u8>, _>(&mut &framed[..]).is_err());
+	}
+}
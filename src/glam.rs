@@ -0,0 +1,130 @@
+// Copyright 2021 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! `glam` vector and quaternion specific serialization.
+//!
+//! The components are encoded in order (`x`, `y`, `z`, `w`), each as the little endian bits of
+//! its IEEE 754 value, i.e. like the `u32` (resp. `u64`) returned by `f32::to_bits` (resp.
+//! `f64::to_bits`). Floats are otherwise not supported by this crate, this encoding preserves
+//! them exactly, including NaN payloads.
+
+use core::mem;
+
+use glam::{Vec2, Vec3, Vec3A, Vec4, Quat, DVec2, DVec3, DVec4, DQuat};
+
+use crate::{Encode, Decode, Input, Output, Error};
+use crate::encode_like::EncodeLike;
+
+macro_rules! impl_for_glam {
+	( $( $type:ty: [$float:ty; $len:expr] as $bits:ty, )* ) => { $(
+		impl Encode for $type {
+			fn size_hint(&self) -> usize {
+				$len * mem::size_of::<$bits>()
+			}
+
+			fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+				for component in self.to_array().iter() {
+					component.to_bits().encode_to(dest);
+				}
+			}
+		}
+
+		impl EncodeLike for $type {}
+
+		impl Decode for $type {
+			fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+				let mut components = [0 as $float; $len];
+				for component in components.iter_mut() {
+					*component = <$float>::from_bits(<$bits>::decode(input)?);
+				}
+				Ok(<$type>::from_array(components))
+			}
+
+			fn encoded_fixed_size() -> Option<usize> {
+				Some($len * mem::size_of::<$bits>())
+			}
+		}
+
+		#[cfg(feature = "max-encoded-len")]
+		impl crate::MaxEncodedLen for $type {
+			fn max_encoded_len() -> usize {
+				$len * mem::size_of::<$bits>()
+			}
+		}
+	)* }
+}
+
+impl_for_glam! {
+	Vec2: [f32; 2] as u32,
+	Vec3: [f32; 3] as u32,
+	Vec3A: [f32; 3] as u32,
+	Vec4: [f32; 4] as u32,
+	Quat: [f32; 4] as u32,
+	DVec2: [f64; 2] as u64,
+	DVec3: [f64; 3] as u64,
+	DVec4: [f64; 4] as u64,
+	DQuat: [f64; 4] as u64,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn vec3_round_trip() {
+		let v = Vec3::new(1.0, -2.5, f32::INFINITY);
+		let encoded = v.encode();
+		assert_eq!(encoded, (1.0f32.to_bits(), (-2.5f32).to_bits(), f32::INFINITY.to_bits()).encode());
+		assert_eq!(encoded.len(), v.size_hint());
+		assert_eq!(Vec3::decode(&mut &encoded[..]), Ok(v));
+
+		let v = Vec3A::new(0.5, 0.25, -0.0);
+		assert_eq!(Vec3A::decode(&mut &v.encode()[..]), Ok(v));
+		assert_eq!(v.encode(), Vec3::from(v).encode());
+
+		let v = DVec3::new(1e300, -1e-300, 3.0);
+		assert_eq!(DVec3::decode(&mut &v.encode()[..]), Ok(v));
+		assert_eq!(v.encode().len(), 3 * 8);
+
+		assert!(Vec3::decode(&mut &encoded[..11]).is_err());
+	}
+
+	#[test]
+	fn quat_round_trip() {
+		let q = Quat::from_xyzw(0.0, 0.6, 0.0, 0.8);
+		let encoded = q.encode();
+		assert_eq!(encoded, [0.0f32, 0.6, 0.0, 0.8].map(f32::to_bits).encode());
+		assert_eq!(Quat::decode(&mut &encoded[..]), Ok(q));
+
+		let q = DQuat::from_xyzw(0.0, 0.6, 0.0, 0.8);
+		assert_eq!(DQuat::decode(&mut &q.encode()[..]), Ok(q));
+	}
+
+	#[test]
+	fn nan_bits_are_preserved() {
+		let nan = f32::from_bits(0x7fc0_1234);
+		let decoded = Vec2::decode(&mut &Vec2::new(nan, 1.0).encode()[..]).unwrap();
+		assert_eq!(decoded.x.to_bits(), 0x7fc0_1234);
+	}
+
+	#[test]
+	#[cfg(feature = "max-encoded-len")]
+	fn glam_max_encoded_len() {
+		use crate::MaxEncodedLen;
+
+		assert_eq!(Vec3::max_encoded_len(), 12);
+		assert_eq!(Quat::max_encoded_len(), 16);
+		assert_eq!(DVec4::max_encoded_len(), 32);
+	}
+}
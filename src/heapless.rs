@@ -0,0 +1,109 @@
+// Copyright 2021 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! `heapless::Vec` specific serialization.
+//!
+//! The encoding is the same as for `Vec<T>`, so no allocator is needed to encode or decode.
+
+use core::mem;
+
+use crate::{
+	Encode, Decode, Input, Output, Error, Compact,
+	codec::{compact_encode_len_to, encode_slice_no_len},
+	encode_like::EncodeLike,
+};
+use crate::alloc::vec::Vec;
+
+impl<T: Encode, const N: usize> Encode for heapless::Vec<T, N> {
+	fn size_hint(&self) -> usize {
+		mem::size_of::<u32>() + mem::size_of::<T>() * self.len()
+	}
+
+	fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+		compact_encode_len_to(dest, self.len()).expect("Compact encodes length");
+
+		encode_slice_no_len(&self[..], dest)
+	}
+}
+
+impl<T: Encode, const N: usize> EncodeLike for heapless::Vec<T, N> {}
+impl<T: EncodeLike<U>, U: Encode, const N: usize> EncodeLike<Vec<U>> for heapless::Vec<T, N> {}
+impl<T: EncodeLike<U>, U: Encode, const N: usize> EncodeLike<heapless::Vec<U, N>> for Vec<T> {}
+
+impl<T: Decode, const N: usize> Decode for heapless::Vec<T, N> {
+	fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+		let Compact(len) = <Compact<u32>>::decode(input)?;
+		if len as usize > N {
+			return Err("Attempt to decode a `heapless::Vec` with more elements than its capacity".into());
+		}
+
+		let mut result = heapless::Vec::new();
+		input.descend_ref()?;
+		for _ in 0..len {
+			result.push(T::decode(input)?)
+				.map_err(|_| "UNEXPECTED ERROR: `len` was checked against the capacity; qed")?;
+		}
+		input.ascend_ref();
+		Ok(result)
+	}
+}
+
+#[cfg(feature = "max-encoded-len")]
+impl<T: crate::MaxEncodedLen, const N: usize> crate::MaxEncodedLen for heapless::Vec<T, N> {
+	fn max_encoded_len() -> usize {
+		use crate::CompactLen;
+		Compact::<u32>::compact_len(&(N as u32))
+			.saturating_add(N.saturating_mul(T::max_encoded_len()))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn heapless_vec_encodes_like_vec() {
+		let mut v = heapless::Vec::<u16, 4>::new();
+		v.extend_from_slice(&[1, 2, 3]).unwrap();
+
+		let encoded = v.encode();
+		assert_eq!(encoded, vec![1u16, 2, 3].encode());
+		assert_eq!(heapless::Vec::<u16, 4>::decode(&mut &encoded[..]), Ok(v));
+	}
+
+	#[test]
+	fn heapless_vec_rejects_over_capacity() {
+		let encoded = vec![1u8, 2, 3, 4, 5].encode();
+		assert!(heapless::Vec::<u8, 4>::decode(&mut &encoded[..]).is_err());
+		assert_eq!(
+			heapless::Vec::<u8, 5>::decode(&mut &encoded[..]).map(|v| v.len()),
+			Ok(5),
+		);
+
+		// Only the length is read before rejecting.
+		let encoded = Compact(u32::max_value()).encode();
+		assert!(heapless::Vec::<u32, 16>::decode(&mut &encoded[..]).is_err());
+	}
+
+	#[test]
+	#[cfg(feature = "max-encoded-len")]
+	fn heapless_vec_max_encoded_len() {
+		use crate::MaxEncodedLen;
+
+		let mut v = heapless::Vec::<u32, 64>::new();
+		v.extend_from_slice(&[u32::max_value(); 64]).unwrap();
+		assert_eq!(v.encode().len(), heapless::Vec::<u32, 64>::max_encoded_len());
+		assert_eq!(heapless::Vec::<u32, 64>::max_encoded_len(), 2 + 64 * 4);
+	}
+}
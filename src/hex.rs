@@ -0,0 +1,76 @@
+// Copyright 2021 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::alloc::vec::Vec;
+use crate::Error;
+
+/// Parse the bytes written as hexadecimal in `hex`, with an optional `0x` prefix.
+///
+/// Both lower and upper case digits are accepted, each byte being written with two digits.
+///
+/// ```
+/// # use parity_scale_codec::from_hex;
+/// assert_eq!(from_hex("0x0100ff"), Ok(vec![1, 0, 255]));
+/// assert!(from_hex("100").is_err());
+/// ```
+pub fn from_hex(hex: &str) -> Result<Vec<u8>, Error> {
+	let hex = hex.strip_prefix("0x").unwrap_or(hex).as_bytes();
+	if hex.len() % 2 == 1 {
+		return Err("Hex string has an odd number of digits".into());
+	}
+
+	hex.chunks(2).map(|pair| Ok(digit(pair[0])? << 4 | digit(pair[1])?)).collect()
+}
+
+fn digit(c: u8) -> Result<u8, Error> {
+	match c {
+		b'0'..=b'9' => Ok(c - b'0'),
+		b'a'..=b'f' => Ok(c - b'a' + 10),
+		b'A'..=b'F' => Ok(c - b'A' + 10),
+		_ => Err("Invalid hex digit".into()),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{Decode, Encode};
+
+	#[test]
+	fn from_hex_parses_bytes() {
+		assert_eq!(from_hex(""), Ok(vec![]));
+		assert_eq!(from_hex("0x"), Ok(vec![]));
+		assert_eq!(from_hex("09afAF"), Ok(vec![0x09, 0xaf, 0xaf]));
+		assert_eq!(from_hex("0x2a"), Ok(vec![42]));
+
+		assert!(from_hex("0").is_err());
+		assert!(from_hex("0xg0").is_err());
+		assert!(from_hex("0X00").is_err());
+		assert!(from_hex(" 00").is_err());
+		assert!(from_hex("é0").is_err());
+	}
+
+	#[test]
+	fn decode_hex_consumes_the_whole_input() {
+		assert_eq!(<(u16, Vec<u8>)>::decode_hex("0x0100080102"), Ok((1, vec![1, 2])));
+		assert_eq!(String::decode_hex(&hex(&"abc".encode())), Ok("abc".to_owned()));
+		assert!(u16::decode_hex("01").is_err());
+		assert!(u16::decode_hex("010000").is_err());
+		assert!(u16::decode_hex("01zz").is_err());
+	}
+
+	fn hex(bytes: &[u8]) -> String {
+		bytes.iter().map(|b| format!("{:02x}", b)).collect()
+	}
+}
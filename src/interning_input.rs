@@ -0,0 +1,157 @@
+// Copyright 2021 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::alloc::{collections::BTreeSet, string::String, sync::Arc, vec::Vec};
+use crate::{Compact, Decode, Error, Input};
+
+/// Input wrapper interning the strings decoded through [`DecodeInterned`].
+///
+/// Equal strings decoded from the same `InterningInput` share a single `Arc<str>` allocation,
+/// which saves memory for payloads repeating the same strings many times. Everything else is
+/// read from the wrapped input as usual, so any `Decode` type can be decoded from it as well.
+///
+/// ```
+/// # use std::sync::Arc;
+/// # use parity_scale_codec::{Encode, DecodeInterned, InterningInput};
+/// let encoded = vec!["alice", "bob", "alice"].encode();
+/// let mut input = InterningInput::new(&encoded[..]);
+/// let names = <Vec<Arc<str>>>::decode_interned(&mut input).unwrap();
+/// assert!(Arc::ptr_eq(&names[0], &names[2]));
+/// ```
+pub struct InterningInput<I> {
+	input: I,
+	strings: BTreeSet<Arc<str>>,
+}
+
+impl<I: Input> InterningInput<I> {
+	/// Create a new instance with an empty cache, reading from `input`.
+	pub fn new(input: I) -> Self {
+		Self { input, strings: BTreeSet::new() }
+	}
+
+	/// Return the wrapped input, dropping the cache.
+	pub fn into_inner(self) -> I {
+		self.input
+	}
+
+	/// Return the shared instance of `string`, adding it to the cache if needed.
+	fn intern(&mut self, string: String) -> Arc<str> {
+		if let Some(interned) = self.strings.get(string.as_str()) {
+			return interned.clone();
+		}
+
+		let interned: Arc<str> = string.into();
+		self.strings.insert(interned.clone());
+		interned
+	}
+}
+
+impl<I: Input> Input for InterningInput<I> {
+	fn remaining_len(&mut self) -> Result<Option<usize>, Error> {
+		self.input.remaining_len()
+	}
+
+	fn read(&mut self, into: &mut [u8]) -> Result<(), Error> {
+		self.input.read(into)
+	}
+
+	fn read_byte(&mut self) -> Result<u8, Error> {
+		self.input.read_byte()
+	}
+
+	fn descend_ref(&mut self) -> Result<(), Error> {
+		self.input.descend_ref()
+	}
+
+	fn ascend_ref(&mut self) {
+		self.input.ascend_ref()
+	}
+}
+
+/// Trait for types which can share the strings they decode through an [`InterningInput`].
+///
+/// The encoding is the same as for [`Decode`]: `Arc<str>` is decoded like a `String`.
+pub trait DecodeInterned: Sized {
+	/// Attempt to decode the value from `input`, sharing the equal strings decoded from it.
+	fn decode_interned<I: Input>(input: &mut InterningInput<I>) -> Result<Self, Error>;
+}
+
+impl DecodeInterned for Arc<str> {
+	fn decode_interned<I: Input>(input: &mut InterningInput<I>) -> Result<Self, Error> {
+		let string = String::decode(input)?;
+		Ok(input.intern(string))
+	}
+}
+
+impl<T: DecodeInterned> DecodeInterned for Option<T> {
+	fn decode_interned<I: Input>(input: &mut InterningInput<I>) -> Result<Self, Error> {
+		match input.read_byte()
+			.map_err(|e| e.chain("Could not decode variant idx for `Option`"))?
+		{
+			0 => Ok(None),
+			1 => Ok(Some(T::decode_interned(input)?)),
+			_ => Err("unexpected first byte decoding Option".into()),
+		}
+	}
+}
+
+impl<T: DecodeInterned> DecodeInterned for Vec<T> {
+	fn decode_interned<I: Input>(input: &mut InterningInput<I>) -> Result<Self, Error> {
+		let len = <Compact<u32>>::decode(input)?.0 as usize;
+
+		// Each item takes at least one byte, don't trust `len` further than that.
+		let capacity = input.remaining_len()?.unwrap_or(0).min(len);
+		let mut result = Vec::with_capacity(capacity);
+		input.descend_ref()?;
+		for _ in 0..len {
+			result.push(T::decode_interned(input)?);
+		}
+		input.ascend_ref();
+		Ok(result)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::Encode;
+
+	#[test]
+	fn equal_strings_share_their_allocation() {
+		let encoded = (vec!["key", "value", "key"], Some("value"), 7u8).encode();
+		let mut input = InterningInput::new(&encoded[..]);
+
+		let strings = <Vec<Arc<str>>>::decode_interned(&mut input).unwrap();
+		let value = <Option<Arc<str>>>::decode_interned(&mut input).unwrap().unwrap();
+		assert_eq!(u8::decode(&mut input), Ok(7));
+		assert!(input.into_inner().is_empty());
+
+		assert_eq!(&*strings[0], "key");
+		assert_eq!(&*strings[1], "value");
+		assert!(Arc::ptr_eq(&strings[0], &strings[2]));
+		assert!(Arc::ptr_eq(&strings[1], &value));
+		assert!(!Arc::ptr_eq(&strings[0], &strings[1]));
+	}
+
+	#[test]
+	fn invalid_strings_are_rejected() {
+		let encoded = vec![vec![0xffu8]].encode();
+		let mut input = InterningInput::new(&encoded[..]);
+		assert!(<Vec<Arc<str>>>::decode_interned(&mut input).is_err());
+
+		let encoded = Compact(u32::max_value()).encode();
+		let mut input = InterningInput::new(&encoded[..]);
+		assert!(<Vec<Arc<str>>>::decode_interned(&mut input).is_err());
+	}
+}
@@ -0,0 +1,175 @@
+// Copyright 2021 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use core::mem;
+
+use crate::alloc::collections::BTreeMap;
+use crate::codec::compact_encode_len_to;
+use crate::encode_like::EncodeLike;
+use crate::framed::{decode_length_delimited, write_framed};
+use crate::{Compact, Decode, Encode, Error, Input, Output};
+
+/// A `BTreeMap<K, V>` whose values are each prefixed by the compact length of their encoding.
+///
+/// **This is not canonical SCALE.** The map is encoded as the compact number of entries, then
+/// each entry in key order as the key, the `Compact<u32>` length of the encoded value and the
+/// encoded value. The length lets a reader skip a value without decoding it, see
+/// [`LengthPrefixedMap::skip_value`].
+///
+/// ```
+/// # use parity_scale_codec::{Encode, Decode, LengthPrefixedMap};
+/// let map = LengthPrefixedMap(vec![(1u8, 2u16)].into_iter().collect());
+/// assert_eq!(map.encode(), vec![1 << 2, 1, 2 << 2, 2, 0]);
+/// assert_eq!(LengthPrefixedMap::decode(&mut &map.encode()[..]), Ok(map));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct LengthPrefixedMap<K, V>(pub BTreeMap<K, V>);
+
+impl<K, V> LengthPrefixedMap<K, V> {
+	/// Skip the length prefixed value of an entry from `input`, without decoding it.
+	///
+	/// `input` must be positioned after the key of the entry, it is left on the key of the next
+	/// entry.
+	pub fn skip_value<I: Input>(input: &mut I) -> Result<(), Error> {
+		let mut len = <Compact<u32>>::decode(input)
+			.map_err(|e| e.chain("Could not decode the value length"))?.0 as usize;
+
+		let mut buf = [0u8; 64];
+		while len > 0 {
+			let chunk = len.min(buf.len());
+			input.read(&mut buf[..chunk])?;
+			len -= chunk;
+		}
+		Ok(())
+	}
+}
+
+impl<K, V> From<BTreeMap<K, V>> for LengthPrefixedMap<K, V> {
+	fn from(map: BTreeMap<K, V>) -> Self {
+		Self(map)
+	}
+}
+
+impl<K, V> From<LengthPrefixedMap<K, V>> for BTreeMap<K, V> {
+	fn from(map: LengthPrefixedMap<K, V>) -> Self {
+		map.0
+	}
+}
+
+impl<K: Encode, V: Encode> Encode for LengthPrefixedMap<K, V> {
+	fn size_hint(&self) -> usize {
+		mem::size_of::<u32>() + self.0.iter()
+			.map(|(k, v)| k.size_hint() + mem::size_of::<u32>() + v.size_hint())
+			.sum::<usize>()
+	}
+
+	fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+		compact_encode_len_to(dest, self.0.len()).expect("Compact encodes length");
+		for (key, value) in &self.0 {
+			key.encode_to(dest);
+			write_framed(dest, value);
+		}
+	}
+}
+
+impl<K: Encode, V: Encode> EncodeLike for LengthPrefixedMap<K, V> {}
+
+impl<K: Decode + Ord, V: Decode> Decode for LengthPrefixedMap<K, V> {
+	fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+		let len = <Compact<u32>>::decode(input)?.0;
+		input.descend_ref()?;
+		let mut map = BTreeMap::new();
+		for _ in 0..len {
+			let key = K::decode(input)?;
+			let value = decode_length_delimited(input)?;
+			map.insert(key, value);
+		}
+		input.ascend_ref();
+		Ok(Self(map))
+	}
+
+	fn skip<I: Input>(input: &mut I) -> Result<(), Error> {
+		let len = <Compact<u32>>::decode(input)?.0;
+		for _ in 0..len {
+			K::skip(input)?;
+			Self::skip_value(input)?;
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::alloc::string::String;
+
+	fn sample() -> LengthPrefixedMap<u16, Vec<String>> {
+		let mut map = BTreeMap::new();
+		map.insert(2, vec!["b".into(), "bb".into()]);
+		map.insert(1, vec!["a".repeat(100)]);
+		map.insert(3, vec![]);
+		LengthPrefixedMap(map)
+	}
+
+	#[test]
+	fn length_prefixed_map_layout() {
+		let map = sample();
+		let encoded = map.encode();
+
+		let mut expected = Compact(3u32).encode();
+		for (key, value) in &map.0 {
+			expected.extend(key.encode());
+			expected.extend(value.encode().encode());
+		}
+		assert_eq!(encoded, expected);
+		assert_eq!(LengthPrefixedMap::decode(&mut &encoded[..]), Ok(map));
+
+		let empty = LengthPrefixedMap::<u8, u8>::default();
+		assert_eq!(empty.encode(), vec![0]);
+		assert_eq!(LengthPrefixedMap::decode(&mut &[0][..]), Ok(empty));
+	}
+
+	#[test]
+	fn skipping_a_value_advances_to_the_next_entry() {
+		type Map = LengthPrefixedMap<u16, Vec<String>>;
+		let encoded = sample().encode();
+		let input = &mut &encoded[..];
+
+		assert_eq!(<Compact<u32>>::decode(input), Ok(Compact(3)));
+		assert_eq!(u16::decode(input), Ok(1));
+		Map::skip_value(input).unwrap();
+		assert_eq!(u16::decode(input), Ok(2));
+		assert_eq!(decode_length_delimited::<Vec<String>, _>(input), Ok(vec!["b".into(), "bb".into()]));
+		assert_eq!(u16::decode(input), Ok(3));
+		Map::skip_value(input).unwrap();
+		assert!(input.is_empty());
+
+		let input = &mut &encoded[..];
+		Map::skip(input).unwrap();
+		assert!(input.is_empty());
+	}
+
+	#[test]
+	fn length_prefixed_map_rejects_mismatching_lengths() {
+		// The value length doesn't match the encoded value.
+		let encoded = (Compact(1u32), 1u8, Compact(3u32), 7u16).encode();
+		assert!(LengthPrefixedMap::<u8, u16>::decode(&mut &encoded[..]).is_err());
+		let encoded = (Compact(1u32), 1u8, Compact(1u32), 7u16).encode();
+		assert!(LengthPrefixedMap::<u8, u16>::decode(&mut &encoded[..]).is_err());
+
+		// The value is cut short.
+		let encoded = (Compact(1u32), 1u8, Compact(2u32), 7u8).encode();
+		assert!(LengthPrefixedMap::<u8, u8>::skip(&mut &encoded[..]).is_err());
+	}
+}
@@ -228,13 +228,28 @@
 //!   you should not get this error/warning again.
 //! - `codec(skip)`: Needs to be placed above a field  or variant and makes it to be skipped while
 //!   encoding/decoding.
+//!   A skipped variant doesn't take an index, which shifts the index of the following variants
+//!   without `codec(index = ..)`.
 //! - `codec(compact)`: Needs to be placed above a field and makes the field use compact encoding.
 //!   (The type needs to support compact encoding.)
 //! - `codec(encoded_as = "OtherType")`: Needs to be placed above a field and makes the field being
 //!   encoded by using `OtherType`.
+//! - `codec(pad = 4)`: Needs to be placed above a field. The field is followed by zero bytes until
+//!   the offset from the first field of the type is a multiple of `4`; decoding skips them. The
+//!   output is not SCALE anymore, this is intended to interoperate with formats aligning their
+//!   fields.
 //! - `codec(index = 0)`: Needs to be placed above an enum variant to make the variant use the given
 //!   index when encoded. By default the index is determined by counting from `0` beginning wth the
 //!   first variant.
+//! - `codec(unknown)`: Needs to be placed above an enum variant. Decoding an unrecognized variant
+//!   index gives this variant instead of an error. If the variant has a single `u8` field, the
+//!   index is stored in it and encoding the variant writes it back.
+//! - `codec(validate = "path::to::fn")`: Needs to be placed above the type. After decoding, the
+//!   derived `Decode` calls the function with `&Self`; a returned `Err` is turned into a decoding
+//!   error.
+//! - `codec(variant_names)`: Needs to be placed above an enum deriving `Encode`, without skipped
+//!   variants. It adds the inherent items `VARIANT_NAMES`, `VARIANT_INDICES` and `variant_index(&self)`
+//!   to map the encoded variant indices to the variant names.
 
 #![warn(missing_docs)]
 #![cfg_attr(not(feature = "std"), no_std)]
@@ -269,37 +284,101 @@ pub mod alloc {
 }
 
 mod codec;
+mod c_like_enum;
 mod compact;
 mod joiner;
 mod keyedvec;
+#[cfg(feature = "std")]
+mod net;
 #[cfg(feature = "bit-vec")]
 mod bit_vec;
 #[cfg(feature = "generic-array")]
 mod generic_array;
+#[cfg(feature = "heapless")]
+mod heapless;
+#[cfg(feature = "bigint")]
+mod bigint;
+#[cfg(feature = "time")]
+mod time;
+#[cfg(feature = "chrono")]
+mod chrono;
+#[cfg(feature = "glam")]
+mod glam;
+#[cfg(feature = "ordered-float")]
+mod ordered_float;
+#[cfg(feature = "fixed")]
+mod fixed;
+#[cfg(feature = "tinyvec")]
+mod tinyvec;
+#[cfg(feature = "semver")]
+mod semver;
 mod decode_all;
+mod decode_cow;
+mod chained_input;
 mod depth_limit;
 mod encode_append;
+mod encoder;
+#[cfg(feature = "debug-input")]
+mod debug_input;
 mod encode_like;
+#[cfg(any(feature = "std", feature = "full"))]
+mod interning_input;
+mod exit_code;
+mod fixed_len_vec;
+mod framed;
+mod length_prefixed_map;
+mod hex;
+#[cfg(feature = "base64")]
+mod base64;
+mod packed_bools;
+mod padding;
 mod error;
+#[cfg(feature = "testing")]
+pub mod testing;
 #[cfg(feature = "max-encoded-len")]
 mod max_encoded_len;
 
 pub use self::error::Error;
 pub use self::codec::{
 	Input, Output, Decode, Encode, Codec, EncodeAsRef, WrapperTypeEncode, WrapperTypeDecode,
-	OptionBool, DecodeLength, FullCodec, FullEncode,
+	OptionBool, OptionNonZero, DecodeLength, FullCodec, FullEncode, scan, decode_vec_exact,
+	decode_versioned, decode_batch, encode_iter, encode_to_yielding,
 };
 #[cfg(feature = "std")]
-pub use self::codec::IoReader;
-pub use self::compact::{Compact, HasCompact, CompactAs, CompactLen};
+pub use self::codec::{IoReader, try_encode_mutex, try_encode_rw_lock};
+#[cfg(feature = "bytes")]
+pub use self::codec::decode_from_bytes;
+#[cfg(feature = "bit-vec")]
+pub use self::bit_vec::{decode_bitslice, try_encode_bitslice};
+pub use self::compact::{
+	Compact, HasCompact, CompactAs, CompactLen, compact_len, Seconds, CompactDuration, Delta, CompactFixed,
+};
 pub use self::joiner::Joiner;
 pub use self::keyedvec::KeyedVec;
 pub use self::decode_all::DecodeAll;
+pub use self::decode_cow::DecodeCow;
+pub use self::chained_input::ChainedInput;
 pub use self::depth_limit::DecodeLimit;
 pub use self::encode_append::EncodeAppend;
+pub use self::encoder::Encoder;
+#[cfg(feature = "debug-input")]
+pub use self::debug_input::{DebugInput, DEBUG_INPUT_LOG_TARGET};
 pub use self::encode_like::{EncodeLike, Ref};
+#[cfg(any(feature = "std", feature = "full"))]
+pub use self::interning_input::{DecodeInterned, InterningInput};
+pub use self::exit_code::ExitCode;
+pub use self::fixed_len_vec::FixedLenVec;
+pub use self::length_prefixed_map::LengthPrefixedMap;
+pub use self::framed::{write_framed, read_framed, decode_length_delimited};
+pub use self::hex::from_hex;
+pub use self::packed_bools::{PackedBools, PackedBoolVec};
+#[doc(hidden)]
+pub use self::padding::{AlignedInput, AlignedOutput};
 #[cfg(feature = "max-encoded-len")]
 pub use max_encoded_len::MaxEncodedLen;
+#[cfg(feature = "max-encoded-len")]
+#[doc(hidden)]
+pub use max_encoded_len::MaxEncodedLenField;
 /// Derive macro for [`MaxEncodedLen`][max_encoded_len::MaxEncodedLen].
 ///
 /// # Examples
@@ -15,9 +15,13 @@
 
 //! `trait MaxEncodedLen` bounds the maximum encoded length of items.
 
-use crate::{Compact, Encode};
+use crate::{Compact, Encode, OptionNonZero};
 use impl_trait_for_tuples::impl_for_tuples;
-use core::{mem, marker::PhantomData};
+use core::{convert::Infallible, mem, marker::{PhantomData, PhantomPinned}};
+use core::num::{
+	NonZeroI8, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI128,
+	NonZeroU8, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU128,
+};
 
 /// Items implementing `MaxEncodedLen` have a statically known maximum encoded size.
 ///
@@ -30,6 +34,26 @@ pub trait MaxEncodedLen: Encode {
 	fn max_encoded_len() -> usize;
 }
 
+/// `MaxEncodedLen` of a field, used by `#[derive(MaxEncodedLen)]`.
+///
+/// Going through this trait, a field type not implementing `MaxEncodedLen` is reported by name
+/// without listing every type which does implement it.
+#[doc(hidden)]
+#[diagnostic::on_unimplemented(
+	message = "`{Self}` does not implement `MaxEncodedLen`",
+	label = "the type of this field does not implement `MaxEncodedLen`",
+)]
+pub trait MaxEncodedLenField {
+	fn field_max_encoded_len() -> usize;
+}
+
+#[diagnostic::do_not_recommend]
+impl<T: MaxEncodedLen> MaxEncodedLenField for T {
+	fn field_max_encoded_len() -> usize {
+		T::max_encoded_len()
+	}
+}
+
 macro_rules! impl_primitives {
 	( $($t:ty),+ ) => {
 		$(
@@ -44,6 +68,48 @@ macro_rules! impl_primitives {
 
 impl_primitives!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, bool);
 
+macro_rules! impl_option_non_zero {
+	( $($t:ty),+ ) => {
+		$(
+			impl MaxEncodedLen for OptionNonZero<$t> {
+				fn max_encoded_len() -> usize {
+					mem::size_of::<$t>()
+				}
+			}
+		)+
+	};
+}
+
+impl_option_non_zero!(
+	NonZeroU8, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU128,
+	NonZeroI8, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI128
+);
+
+macro_rules! impl_atomics {
+	( $( $target_has_atomic:literal: $name:ident => $t:ty ),* $(,)? ) => {
+		$(
+			#[cfg(target_has_atomic = $target_has_atomic)]
+			impl MaxEncodedLen for core::sync::atomic::$name {
+				fn max_encoded_len() -> usize {
+					<$t>::max_encoded_len()
+				}
+			}
+		)*
+	};
+}
+
+impl_atomics!(
+	"8": AtomicBool => bool,
+	"8": AtomicI8 => i8,
+	"16": AtomicI16 => i16,
+	"32": AtomicI32 => i32,
+	"64": AtomicI64 => i64,
+	"8": AtomicU8 => u8,
+	"16": AtomicU16 => u16,
+	"32": AtomicU32 => u32,
+	"64": AtomicU64 => u64,
+);
+
 macro_rules! impl_compact {
 	($( $t:ty => $e:expr; )*) => {
 		$(
@@ -79,6 +145,10 @@ impl MaxEncodedLen for Tuple {
 	}
 }
 
+/// The maximum encoded length of an array is `N` times the one of its element.
+///
+/// Note that as a trait method, `max_encoded_len` can not be called in a const context, e.g. to
+/// size a buffer, on stable Rust.
 impl<T: MaxEncodedLen, const N: usize> MaxEncodedLen for [T; N] {
 	fn max_encoded_len() -> usize {
 		T::max_encoded_len().saturating_mul(N)
@@ -101,12 +171,63 @@ where
 	}
 }
 
+impl<B, C> MaxEncodedLen for core::ops::ControlFlow<B, C>
+where
+	B: MaxEncodedLen,
+	C: MaxEncodedLen,
+{
+	fn max_encoded_len() -> usize {
+		B::max_encoded_len().max(C::max_encoded_len()).saturating_add(1)
+	}
+}
+
 impl<T> MaxEncodedLen for PhantomData<T> {
 	fn max_encoded_len() -> usize {
 		0
 	}
 }
 
+impl<T: MaxEncodedLen> MaxEncodedLen for core::num::Wrapping<T> {
+	fn max_encoded_len() -> usize {
+		T::max_encoded_len()
+	}
+}
+
+impl<T: MaxEncodedLen> MaxEncodedLen for core::ops::RangeFrom<T> {
+	fn max_encoded_len() -> usize {
+		T::max_encoded_len()
+	}
+}
+
+impl<T: MaxEncodedLen> MaxEncodedLen for core::ops::RangeTo<T> {
+	fn max_encoded_len() -> usize {
+		T::max_encoded_len()
+	}
+}
+impl<T: MaxEncodedLen> MaxEncodedLen for core::ops::RangeToInclusive<T> {
+	fn max_encoded_len() -> usize {
+		T::max_encoded_len()
+	}
+}
+
+impl MaxEncodedLen for core::ops::RangeFull {
+	fn max_encoded_len() -> usize {
+		0
+	}
+}
+
+impl MaxEncodedLen for PhantomPinned {
+	fn max_encoded_len() -> usize {
+		0
+	}
+}
+
+impl MaxEncodedLen for Infallible {
+	fn max_encoded_len() -> usize {
+		0
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
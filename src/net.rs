@@ -0,0 +1,259 @@
+// Copyright 2021 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! `std::net` address specific serialization.
+//!
+//! IP addresses are encoded as their octets, in network order. Ports and the IPv6 `flowinfo` and
+//! `scope_id` are encoded like the integers they are, i.e. little endian. `IpAddr` and
+//! `SocketAddr` are encoded like enums, with `V4` as `0` and `V6` as `1`.
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+
+use crate::{Encode, Decode, Input, Output, Error};
+use crate::encode_like::EncodeLike;
+
+impl Encode for Ipv4Addr {
+	fn size_hint(&self) -> usize {
+		4
+	}
+
+	fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+		dest.write(&self.octets())
+	}
+}
+
+impl EncodeLike for Ipv4Addr {}
+
+impl Decode for Ipv4Addr {
+	fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+		<[u8; 4]>::decode(input).map(Self::from)
+	}
+
+	fn encoded_fixed_size() -> Option<usize> {
+		Some(4)
+	}
+}
+
+impl Encode for Ipv6Addr {
+	fn size_hint(&self) -> usize {
+		16
+	}
+
+	fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+		dest.write(&self.octets())
+	}
+}
+
+impl EncodeLike for Ipv6Addr {}
+
+impl Decode for Ipv6Addr {
+	fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+		<[u8; 16]>::decode(input).map(Self::from)
+	}
+
+	fn encoded_fixed_size() -> Option<usize> {
+		Some(16)
+	}
+}
+
+impl Encode for SocketAddrV4 {
+	fn size_hint(&self) -> usize {
+		4 + 2
+	}
+
+	fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+		self.ip().encode_to(dest);
+		self.port().encode_to(dest);
+	}
+}
+
+impl EncodeLike for SocketAddrV4 {}
+
+impl Decode for SocketAddrV4 {
+	fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+		Ok(Self::new(Ipv4Addr::decode(input)?, u16::decode(input)?))
+	}
+
+	fn encoded_fixed_size() -> Option<usize> {
+		Some(4 + 2)
+	}
+}
+
+impl Encode for SocketAddrV6 {
+	fn size_hint(&self) -> usize {
+		16 + 2 + 4 + 4
+	}
+
+	fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+		self.ip().encode_to(dest);
+		self.port().encode_to(dest);
+		self.flowinfo().encode_to(dest);
+		self.scope_id().encode_to(dest);
+	}
+}
+
+impl EncodeLike for SocketAddrV6 {}
+
+impl Decode for SocketAddrV6 {
+	fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+		Ok(Self::new(
+			Ipv6Addr::decode(input)?,
+			u16::decode(input)?,
+			u32::decode(input)?,
+			u32::decode(input)?,
+		))
+	}
+
+	fn encoded_fixed_size() -> Option<usize> {
+		Some(16 + 2 + 4 + 4)
+	}
+}
+
+macro_rules! impl_for_v4_or_v6 {
+	( $( $type:ident($v4:ty, $v6:ty) = $name:expr, )* ) => { $(
+		impl Encode for $type {
+			fn size_hint(&self) -> usize {
+				1 + match self {
+					$type::V4(v4) => v4.size_hint(),
+					$type::V6(v6) => v6.size_hint(),
+				}
+			}
+
+			fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+				match self {
+					$type::V4(v4) => {
+						dest.push_byte(0);
+						v4.encode_to(dest);
+					},
+					$type::V6(v6) => {
+						dest.push_byte(1);
+						v6.encode_to(dest);
+					},
+				}
+			}
+		}
+
+		impl EncodeLike for $type {}
+
+		impl Decode for $type {
+			fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+				match input.read_byte()
+					.map_err(|e| e.chain(concat!("Could not decode variant idx for `", $name, "`")))?
+				{
+					0 => <$v4>::decode(input).map($type::V4),
+					1 => <$v6>::decode(input).map($type::V6),
+					_ => Err(concat!("Invalid variant idx for `", $name, "`").into()),
+				}
+			}
+		}
+
+		#[cfg(feature = "max-encoded-len")]
+		impl crate::MaxEncodedLen for $type {
+			fn max_encoded_len() -> usize {
+				1 + <$v6 as crate::MaxEncodedLen>::max_encoded_len()
+			}
+		}
+	)* }
+}
+
+impl_for_v4_or_v6! {
+	IpAddr(Ipv4Addr, Ipv6Addr) = "IpAddr",
+	SocketAddr(SocketAddrV4, SocketAddrV6) = "SocketAddr",
+}
+
+#[cfg(feature = "max-encoded-len")]
+mod max_encoded_len {
+	use super::*;
+	use crate::MaxEncodedLen;
+
+	impl MaxEncodedLen for Ipv4Addr {
+		fn max_encoded_len() -> usize {
+			4
+		}
+	}
+
+	impl MaxEncodedLen for Ipv6Addr {
+		fn max_encoded_len() -> usize {
+			16
+		}
+	}
+
+	impl MaxEncodedLen for SocketAddrV4 {
+		fn max_encoded_len() -> usize {
+			4 + 2
+		}
+	}
+
+	impl MaxEncodedLen for SocketAddrV6 {
+		fn max_encoded_len() -> usize {
+			16 + 2 + 4 + 4
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn socket_addr_v4_round_trip() {
+		let addr = SocketAddrV4::new(Ipv4Addr::new(192, 168, 0, 1), 0x1f90);
+		let encoded = addr.encode();
+		assert_eq!(encoded, vec![192, 168, 0, 1, 0x90, 0x1f]);
+		assert_eq!(encoded.len(), addr.size_hint());
+		assert_eq!(SocketAddrV4::decode(&mut &encoded[..]), Ok(addr));
+	}
+
+	#[test]
+	fn socket_addr_v6_round_trip() {
+		let ip = Ipv6Addr::new(0xfe80, 0, 0, 0, 0, 0, 0, 1);
+		let addr = SocketAddrV6::new(ip, 443, 0x0001_2345, 7);
+		let encoded = addr.encode();
+		assert_eq!(encoded, (ip.octets(), 443u16, 0x0001_2345u32, 7u32).encode());
+		assert_eq!(encoded.len(), addr.size_hint());
+
+		let decoded = SocketAddrV6::decode(&mut &encoded[..]).unwrap();
+		assert_eq!(decoded, addr);
+		assert_eq!(decoded.flowinfo(), 0x0001_2345);
+		assert_eq!(decoded.scope_id(), 7);
+
+		assert!(SocketAddrV6::decode(&mut &encoded[..encoded.len() - 1]).is_err());
+	}
+
+	#[test]
+	fn ip_and_socket_addr_enums() {
+		let v4 = IpAddr::V4(Ipv4Addr::LOCALHOST);
+		assert_eq!(v4.encode(), vec![0, 127, 0, 0, 1]);
+		assert_eq!(IpAddr::decode(&mut &v4.encode()[..]), Ok(v4));
+
+		let v6 = SocketAddr::V6(SocketAddrV6::new(Ipv6Addr::LOCALHOST, 80, 1, 2));
+		let encoded = v6.encode();
+		assert_eq!(encoded[0], 1);
+		assert_eq!(encoded.len(), v6.size_hint());
+		assert_eq!(SocketAddr::decode(&mut &encoded[..]), Ok(v6));
+
+		assert!(IpAddr::decode(&mut &[2, 127, 0, 0, 1][..]).is_err());
+	}
+
+	#[test]
+	#[cfg(feature = "max-encoded-len")]
+	fn net_max_encoded_len() {
+		use crate::MaxEncodedLen;
+
+		assert_eq!(SocketAddrV4::max_encoded_len(), 6);
+		assert_eq!(SocketAddrV6::max_encoded_len(), 26);
+		assert_eq!(IpAddr::max_encoded_len(), 17);
+		assert_eq!(SocketAddr::max_encoded_len(), 27);
+	}
+}
@@ -0,0 +1,143 @@
+// Copyright 2021 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! `ordered-float` specific serialization.
+//!
+//! `OrderedFloat<F>` and `NotNan<F>` are encoded as the little endian bits of the IEEE 754 value
+//! they wrap, i.e. like the integer returned by `to_bits`. Decoding a `NotNan` fails on a NaN.
+
+use core::mem;
+
+use ordered_float::{NotNan, OrderedFloat};
+
+use crate::{Encode, Decode, Input, Output, Error};
+use crate::encode_like::EncodeLike;
+
+macro_rules! impl_for_ordered_float {
+	( $( $float:ty as $bits:ty, )* ) => { $(
+		impl Encode for OrderedFloat<$float> {
+			fn size_hint(&self) -> usize {
+				mem::size_of::<$bits>()
+			}
+
+			fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+				self.0.to_bits().encode_to(dest)
+			}
+		}
+
+		impl EncodeLike for OrderedFloat<$float> {}
+
+		impl Decode for OrderedFloat<$float> {
+			fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+				<$bits>::decode(input).map(|bits| OrderedFloat(<$float>::from_bits(bits)))
+			}
+
+			fn encoded_fixed_size() -> Option<usize> {
+				Some(mem::size_of::<$bits>())
+			}
+		}
+
+		impl Encode for NotNan<$float> {
+			fn size_hint(&self) -> usize {
+				mem::size_of::<$bits>()
+			}
+
+			fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+				self.into_inner().to_bits().encode_to(dest)
+			}
+		}
+
+		impl EncodeLike for NotNan<$float> {}
+
+		impl Decode for NotNan<$float> {
+			fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+				let bits = <$bits>::decode(input)?;
+				NotNan::new(<$float>::from_bits(bits))
+					.map_err(|_| "NaN is not a valid `NotNan`".into())
+			}
+
+			fn encoded_fixed_size() -> Option<usize> {
+				Some(mem::size_of::<$bits>())
+			}
+		}
+
+		#[cfg(feature = "max-encoded-len")]
+		impl crate::MaxEncodedLen for OrderedFloat<$float> {
+			fn max_encoded_len() -> usize {
+				mem::size_of::<$bits>()
+			}
+		}
+
+		#[cfg(feature = "max-encoded-len")]
+		impl crate::MaxEncodedLen for NotNan<$float> {
+			fn max_encoded_len() -> usize {
+				mem::size_of::<$bits>()
+			}
+		}
+	)* }
+}
+
+impl_for_ordered_float! {
+	f32 as u32,
+	f64 as u64,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::alloc::collections::BTreeMap;
+
+	#[test]
+	fn ordered_float_roundtrip() {
+		for value in [0.0, -0.0, 1.5, -2.25, f64::INFINITY, f64::MIN_POSITIVE, f64::NAN].iter() {
+			let value = OrderedFloat(*value);
+			let encoded = value.encode();
+			assert_eq!(encoded, value.0.to_bits().encode());
+			assert_eq!(encoded.len(), value.size_hint());
+
+			let decoded = OrderedFloat::<f64>::decode(&mut &encoded[..]).unwrap();
+			assert_eq!(decoded.0.to_bits(), value.0.to_bits());
+		}
+
+		let value = OrderedFloat(0.1f32);
+		assert_eq!(value.encode(), 0.1f32.to_bits().encode());
+		assert_eq!(OrderedFloat::<f32>::decode(&mut &value.encode()[..]), Ok(value));
+	}
+
+	#[test]
+	fn ordered_float_map_keys() {
+		let map: BTreeMap<_, _> = vec![(OrderedFloat(2.5f64), 1u8), (OrderedFloat(-1.0), 2)].into_iter().collect();
+		assert_eq!(<BTreeMap<OrderedFloat<f64>, u8>>::decode(&mut &map.encode()[..]), Ok(map));
+	}
+
+	#[test]
+	fn not_nan_roundtrip() {
+		let value = NotNan::new(-3.75f64).unwrap();
+		let encoded = value.encode();
+		assert_eq!(encoded, (-3.75f64).to_bits().encode());
+		assert_eq!(NotNan::<f64>::decode(&mut &encoded[..]), Ok(value));
+
+		let value = NotNan::new(f32::NEG_INFINITY).unwrap();
+		assert_eq!(NotNan::<f32>::decode(&mut &value.encode()[..]), Ok(value));
+	}
+
+	#[test]
+	fn not_nan_rejects_nan() {
+		assert!(NotNan::<f64>::decode(&mut &f64::NAN.to_bits().encode()[..]).is_err());
+		// Any NaN payload, whatever its sign.
+		assert!(NotNan::<f64>::decode(&mut &u64::max_value().encode()[..]).is_err());
+		assert!(NotNan::<f64>::decode(&mut &0x7ff0_0000_0000_0001u64.encode()[..]).is_err());
+		assert!(NotNan::<f32>::decode(&mut &f32::NAN.to_bits().encode()[..]).is_err());
+	}
+}
@@ -0,0 +1,289 @@
+// Copyright 2021 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Bit packed encoding of `bool` arrays and vectors.
+
+use crate::{Encode, Decode, Input, Output, Error, Compact, CompactLen};
+use crate::alloc::vec::Vec;
+use crate::codec::decode_vec_with_len;
+use crate::encode_like::EncodeLike;
+
+/// Write `bools` as bits, most significant bit first, padding the last byte with zeros.
+fn encode_packed<W: Output + ?Sized>(bools: &[bool], dest: &mut W) {
+	for chunk in bools.chunks(8) {
+		let byte = chunk.iter()
+			.enumerate()
+			.fold(0u8, |byte, (i, bit)| byte | ((*bit as u8) << (7 - i)));
+		dest.push_byte(byte);
+	}
+}
+
+/// Fill `bools` from the bits read from `input`, checking that the padding bits are zero.
+fn decode_packed<I: Input>(bools: &mut [bool], input: &mut I) -> Result<(), Error> {
+	for chunk in bools.chunks_mut(8) {
+		let byte = input.read_byte()?;
+		if byte & 0xffu8.checked_shr(chunk.len() as u32).unwrap_or(0) != 0 {
+			return Err("Padding bits of packed bools must be zero".into());
+		}
+
+		for (i, bit) in chunk.iter_mut().enumerate() {
+			*bit = byte & (1 << (7 - i)) != 0;
+		}
+	}
+
+	Ok(())
+}
+
+/// A `[bool; N]` that is encoded packed into `ceil(N / 8)` bytes.
+///
+/// `[bool; N]` itself keeps its one byte per element encoding. Wrap it in `PackedBools` to
+/// encode the flags as bits instead, most significant bit first. This is the same layout as a
+/// `BitVec<u8, Msb0>`, but without length prefix as `N` is known.
+///
+/// The padding bits of the last byte must be zero, decoding fails otherwise.
+///
+/// ```
+/// # use parity_scale_codec::{Encode, Decode, PackedBools};
+/// let flags = PackedBools([true, false, false, false, false, false, false, true, true]);
+/// assert_eq!(flags.encode(), vec![0b1000_0001, 0b1000_0000]);
+/// assert_eq!(PackedBools::decode(&mut &[0b1000_0001, 0b1000_0000][..]), Ok(flags));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PackedBools<const N: usize>(pub [bool; N]);
+
+impl<const N: usize> PackedBools<N> {
+	/// The number of bytes used by the encoding.
+	pub const ENCODED_LEN: usize = N.div_ceil(8);
+}
+
+impl<const N: usize> From<[bool; N]> for PackedBools<N> {
+	fn from(bools: [bool; N]) -> Self {
+		Self(bools)
+	}
+}
+
+impl<const N: usize> From<PackedBools<N>> for [bool; N] {
+	fn from(packed: PackedBools<N>) -> Self {
+		packed.0
+	}
+}
+
+impl<const N: usize> Encode for PackedBools<N> {
+	fn size_hint(&self) -> usize {
+		Self::ENCODED_LEN
+	}
+
+	fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+		encode_packed(&self.0, dest)
+	}
+}
+
+impl<const N: usize> EncodeLike for PackedBools<N> {}
+
+impl<const N: usize> Decode for PackedBools<N> {
+	fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+		let mut bools = [false; N];
+		decode_packed(&mut bools, input)?;
+		Ok(Self(bools))
+	}
+}
+
+#[cfg(feature = "max-encoded-len")]
+impl<const N: usize> crate::MaxEncodedLen for PackedBools<N> {
+	fn max_encoded_len() -> usize {
+		Self::ENCODED_LEN
+	}
+}
+
+/// A `Vec<bool>` that is encoded as its compact length in bits followed by the packed bits.
+///
+/// `Vec<bool>` itself keeps its one byte per element encoding. Wrap it in `PackedBoolVec` to
+/// encode it with the same layout as a `BitVec<u8, Msb0>`, without depending on `bitvec`.
+///
+/// Like for [`PackedBools`], the padding bits of the last byte must be zero.
+///
+/// ```
+/// # use parity_scale_codec::{Encode, Decode, PackedBoolVec};
+/// let flags = PackedBoolVec(vec![true, false, true]);
+/// assert_eq!(flags.encode(), vec![3 << 2, 0b1010_0000]);
+/// assert_eq!(PackedBoolVec::decode(&mut &[3 << 2, 0b1010_0000][..]), Ok(flags));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct PackedBoolVec(pub Vec<bool>);
+
+impl From<Vec<bool>> for PackedBoolVec {
+	fn from(bools: Vec<bool>) -> Self {
+		Self(bools)
+	}
+}
+
+impl From<PackedBoolVec> for Vec<bool> {
+	fn from(packed: PackedBoolVec) -> Self {
+		packed.0
+	}
+}
+
+/// The number of bytes holding `len` packed bools, without overflowing on 32 bit targets.
+fn packed_len(len: usize) -> usize {
+	len.div_ceil(8)
+}
+
+impl Encode for PackedBoolVec {
+	fn size_hint(&self) -> usize {
+		Compact::<u32>::compact_len(&(self.0.len() as u32)) + packed_len(self.0.len())
+	}
+
+	fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+		assert!(
+			self.0.len() <= u32::MAX as usize,
+			"Attempted to serialize a collection with too many elements.",
+		);
+		Compact(self.0.len() as u32).encode_to(dest);
+		encode_packed(&self.0, dest)
+	}
+}
+
+impl EncodeLike for PackedBoolVec {}
+
+impl Decode for PackedBoolVec {
+	fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+		let len = <Compact<u32>>::decode(input)?.0 as usize;
+		// Read the bytes first, so the preallocation is bounded by the input length.
+		let bytes = decode_vec_with_len::<u8, _>(input, packed_len(len))?;
+
+		let mut bools = crate::alloc::vec![false; len];
+		decode_packed(&mut bools, &mut &bytes[..])?;
+		Ok(Self(bools))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn packed_bools_8() {
+		let flags = PackedBools([true, true, false, true, false, false, false, true]);
+		let encoded = flags.encode();
+		assert_eq!(encoded, vec![0b1101_0001]);
+		assert_eq!(PackedBools::<8>::decode(&mut &encoded[..]), Ok(flags));
+
+		assert_eq!(PackedBools([true; 8]).encode(), vec![0xff]);
+		assert_eq!(PackedBools::<8>::decode(&mut &[0xff][..]), Ok(PackedBools([true; 8])));
+	}
+
+	#[test]
+	fn packed_bools_9() {
+		let mut bools = [false; 9];
+		bools[0] = true;
+		bools[8] = true;
+		let flags = PackedBools(bools);
+
+		let encoded = flags.encode();
+		assert_eq!(encoded, vec![0b1000_0000, 0b1000_0000]);
+		assert_eq!(encoded.len(), PackedBools::<9>::ENCODED_LEN);
+		assert_eq!(PackedBools::<9>::decode(&mut &encoded[..]), Ok(flags));
+
+		assert!(PackedBools::<9>::decode(&mut &[0, 0b0100_0000][..]).is_err());
+		assert!(PackedBools::<9>::decode(&mut &[0][..]).is_err());
+	}
+
+	#[test]
+	fn packed_bools_0() {
+		let flags = PackedBools::<0>([]);
+		assert!(flags.encode().is_empty());
+		assert_eq!(PackedBools::<0>::decode(&mut &[][..]), Ok(flags));
+	}
+
+	#[test]
+	fn packed_bool_vec_bit_count_boundaries() {
+		for &(len, ref expected) in &[
+			(7, vec![7 << 2, 0b1000_0010]),
+			(8, vec![8 << 2, 0b1000_0001]),
+			(9, vec![9 << 2, 0b1000_0000, 0b1000_0000]),
+		] {
+			let mut bools = vec![false; len];
+			bools[0] = true;
+			bools[len - 1] = true;
+			let flags = PackedBoolVec(bools);
+
+			let encoded = flags.encode();
+			assert_eq!(&encoded, expected);
+			assert_eq!(encoded.len(), flags.size_hint());
+			assert_eq!(PackedBoolVec::decode(&mut &encoded[..]), Ok(flags));
+		}
+
+		assert_eq!(PackedBoolVec(vec![]).encode(), vec![0]);
+		assert_eq!(PackedBoolVec::decode(&mut &[0][..]), Ok(PackedBoolVec(vec![])));
+	}
+
+	#[test]
+	fn packed_bool_vec_invalid() {
+		// Non zero padding bit.
+		assert!(PackedBoolVec::decode(&mut &[7 << 2, 0b0000_0001][..]).is_err());
+		// Missing byte.
+		assert!(PackedBoolVec::decode(&mut &[9 << 2, 0][..]).is_err());
+		// Huge length without data.
+		assert!(PackedBoolVec::decode(&mut &Compact(u32::MAX).encode()[..]).is_err());
+		assert!(PackedBoolVec::decode(&mut &(Compact(u32::MAX), [0xffu8; 16]).encode()[..]).is_err());
+	}
+
+	#[test]
+	fn packed_len_does_not_overflow() {
+		assert_eq!(packed_len(0), 0);
+		assert_eq!(packed_len(1), 1);
+		assert_eq!(packed_len(8), 1);
+		assert_eq!(packed_len(9), 2);
+		assert_eq!(packed_len(u32::MAX as usize), (1 << 29));
+		assert_eq!(packed_len(usize::MAX), usize::MAX / 8 + 1);
+	}
+
+	#[test]
+	#[cfg(feature = "bit-vec")]
+	fn packed_bool_vec_matches_bit_vec_msb0() {
+		use bitvec::{vec::BitVec, order::Msb0};
+
+		for len in 0..20 {
+			let bools: Vec<bool> = (0..len).map(|i| i % 3 == 0).collect();
+			let bit_vec: BitVec<u8, Msb0> = bools.iter().copied().collect();
+
+			let encoded = bit_vec.encode();
+			assert_eq!(PackedBoolVec(bools.clone()).encode(), encoded);
+			assert_eq!(PackedBoolVec::decode(&mut &encoded[..]), Ok(PackedBoolVec(bools)));
+		}
+	}
+
+	#[test]
+	#[cfg(feature = "bit-vec")]
+	fn packed_bools_match_bit_vec_msb0() {
+		use bitvec::{vec::BitVec, order::Msb0};
+
+		let bools = [true, false, true, true, false, false, true, false, true, true, false];
+		let bit_vec: BitVec<u8, Msb0> = bools.iter().copied().collect();
+
+		let encoded = bit_vec.encode();
+		let prefix_len = <crate::Compact<u32> as crate::CompactLen<u32>>::compact_len(&(bools.len() as u32));
+		assert_eq!(PackedBools(bools).encode(), &encoded[prefix_len..]);
+	}
+
+	#[test]
+	#[cfg(feature = "max-encoded-len")]
+	fn packed_bools_max_encoded_len() {
+		use crate::MaxEncodedLen;
+
+		assert_eq!(PackedBools::<0>::max_encoded_len(), 0);
+		assert_eq!(PackedBools::<8>::max_encoded_len(), 1);
+		assert_eq!(PackedBools::<9>::max_encoded_len(), 2);
+	}
+}
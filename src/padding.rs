@@ -0,0 +1,140 @@
+// Copyright 2021 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! !INTERNAL USE ONLY!
+//!
+//! Support for the `#[codec(pad = N)]` field attribute of the derive macros.
+//!
+//! The fields of a struct or variant are encoded and decoded through these wrappers, which track
+//! the offset from the first field. After a padded field, zero bytes are written (or bytes are
+//! skipped) until the offset is a multiple of `N`.
+
+use crate::{Error, Input, Output};
+
+/// Output counting the bytes written, so that padding can be inserted.
+#[doc(hidden)]
+pub struct AlignedOutput<'a, W: ?Sized> {
+	dest: &'a mut W,
+	offset: usize,
+}
+
+impl<'a, W: Output + ?Sized> AlignedOutput<'a, W> {
+	/// Start counting at offset zero.
+	pub fn new(dest: &'a mut W) -> Self {
+		Self { dest, offset: 0 }
+	}
+
+	/// Write zero bytes until the offset is a multiple of `align`.
+	pub fn align_to(&mut self, align: usize) {
+		self.write_byte_repeated(0, (align - self.offset % align) % align);
+	}
+}
+
+impl<'a, W: Output + ?Sized> Output for AlignedOutput<'a, W> {
+	fn write(&mut self, bytes: &[u8]) {
+		self.offset += bytes.len();
+		self.dest.write(bytes)
+	}
+
+	fn push_byte(&mut self, byte: u8) {
+		self.offset += 1;
+		self.dest.push_byte(byte)
+	}
+
+	fn write_byte_repeated(&mut self, byte: u8, count: usize) {
+		self.offset += count;
+		self.dest.write_byte_repeated(byte, count)
+	}
+}
+
+/// Input counting the bytes read, so that padding can be skipped.
+#[doc(hidden)]
+pub struct AlignedInput<'a, I> {
+	input: &'a mut I,
+	offset: usize,
+}
+
+impl<'a, I: Input> AlignedInput<'a, I> {
+	/// Start counting at offset zero.
+	pub fn new(input: &'a mut I) -> Self {
+		Self { input, offset: 0 }
+	}
+
+	/// Skip bytes until the offset is a multiple of `align`.
+	///
+	/// The padding bytes are not checked, as formats using padding usually leave them unspecified.
+	pub fn align_to(&mut self, align: usize) -> Result<(), Error> {
+		for _ in 0..(align - self.offset % align) % align {
+			self.read_byte().map_err(|e| e.chain("Could not skip padding"))?;
+		}
+
+		Ok(())
+	}
+}
+
+impl<'a, I: Input> Input for AlignedInput<'a, I> {
+	fn remaining_len(&mut self) -> Result<Option<usize>, Error> {
+		self.input.remaining_len()
+	}
+
+	fn read(&mut self, into: &mut [u8]) -> Result<(), Error> {
+		self.input.read(into)?;
+		self.offset += into.len();
+		Ok(())
+	}
+
+	fn read_byte(&mut self) -> Result<u8, Error> {
+		let byte = self.input.read_byte()?;
+		self.offset += 1;
+		Ok(byte)
+	}
+
+	fn descend_ref(&mut self) -> Result<(), Error> {
+		self.input.descend_ref()
+	}
+
+	fn ascend_ref(&mut self) {
+		self.input.ascend_ref()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{Decode, Encode};
+
+	#[test]
+	fn aligned_output_pads_to_offset() {
+		let mut dest = Vec::new();
+		let mut aligned = AlignedOutput::new(&mut dest);
+		aligned.align_to(4);
+		1u8.encode_to(&mut aligned);
+		aligned.align_to(4);
+		2u16.encode_to(&mut aligned);
+		aligned.align_to(2);
+		3u32.encode_to(&mut aligned);
+		aligned.align_to(4);
+		assert_eq!(dest, vec![1, 0, 0, 0, 2, 0, 3, 0, 0, 0, 0, 0]);
+	}
+
+	#[test]
+	fn aligned_input_skips_padding() {
+		let mut input = &[1, 0xff, 0xff, 0xff, 2, 0][..];
+		let mut aligned = AlignedInput::new(&mut input);
+		assert_eq!(u8::decode(&mut aligned), Ok(1));
+		aligned.align_to(4).unwrap();
+		assert_eq!(u8::decode(&mut aligned), Ok(2));
+		assert!(aligned.align_to(4).is_err());
+	}
+}
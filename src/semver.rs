@@ -0,0 +1,103 @@
+// Copyright 2021 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! `semver` specific serialization.
+//!
+//! A `Version` is encoded as its major, minor and patch numbers, each as a `Compact<u64>`,
+//! followed by its pre-release and build metadata, each as a `String` (empty when absent).
+
+use semver::{BuildMetadata, Prerelease, Version};
+
+use crate::{Encode, Decode, Input, Output, Error, Compact, CompactLen};
+use crate::alloc::{string::String, vec::Vec};
+use crate::encode_like::EncodeLike;
+
+/// Length of the encoding of `s`.
+fn str_len(s: &str) -> usize {
+	Compact::<u32>::compact_len(&(s.len() as u32)) + s.len()
+}
+
+/// Decode a string, also without the `full` feature.
+fn decode_str<I: Input>(input: &mut I) -> Result<String, Error> {
+	String::from_utf8(Vec::decode(input)?).map_err(|_| "Invalid utf8 sequence".into())
+}
+
+impl Encode for Version {
+	fn size_hint(&self) -> usize {
+		Compact::<u64>::compact_len(&self.major)
+			+ Compact::<u64>::compact_len(&self.minor)
+			+ Compact::<u64>::compact_len(&self.patch)
+			+ str_len(self.pre.as_str())
+			+ str_len(self.build.as_str())
+	}
+
+	fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+		Compact(self.major).encode_to(dest);
+		Compact(self.minor).encode_to(dest);
+		Compact(self.patch).encode_to(dest);
+		self.pre.as_str().encode_to(dest);
+		self.build.as_str().encode_to(dest);
+	}
+}
+
+impl EncodeLike for Version {}
+
+impl Decode for Version {
+	fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+		let major = <Compact<u64>>::decode(input)?.0;
+		let minor = <Compact<u64>>::decode(input)?.0;
+		let patch = <Compact<u64>>::decode(input)?.0;
+		let pre = Prerelease::new(&decode_str(input)?)
+			.map_err(|_| "Invalid `Version` pre-release")?;
+		let build = BuildMetadata::new(&decode_str(input)?)
+			.map_err(|_| "Invalid `Version` build metadata")?;
+
+		Ok(Version { major, minor, patch, pre, build })
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn version_roundtrip() {
+		let version = Version::new(1, 2, 300);
+		let encoded = version.encode();
+		assert_eq!(encoded, vec![4, 8, 0xb1, 0x04, 0, 0]);
+		assert_eq!(encoded.len(), version.size_hint());
+		assert_eq!(Version::decode(&mut &encoded[..]), Ok(version));
+
+		let version = Version::parse("0.10.0-rc.1+build.5").unwrap();
+		let encoded = version.encode();
+		assert_eq!(
+			encoded,
+			(Compact(0u64), Compact(10u64), Compact(0u64), "rc.1", "build.5").encode(),
+		);
+		assert_eq!(encoded.len(), version.size_hint());
+		assert_eq!(Version::decode(&mut &encoded[..]), Ok(version));
+	}
+
+	#[test]
+	fn version_rejects_invalid_metadata() {
+		let encoded = (Compact(1u64), Compact(0u64), Compact(0u64), "rc..1", "").encode();
+		assert!(Version::decode(&mut &encoded[..]).is_err());
+
+		let encoded = (Compact(1u64), Compact(0u64), Compact(0u64), "", "build+1").encode();
+		assert!(Version::decode(&mut &encoded[..]).is_err());
+
+		let encoded = (Compact(1u64), Compact(0u64), Compact(0u64), "").encode();
+		assert!(Version::decode(&mut &encoded[..]).is_err());
+	}
+}
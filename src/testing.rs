@@ -0,0 +1,88 @@
+// Copyright 2021 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Helpers for testing the codec implementations of a type.
+
+use core::fmt::Debug;
+
+use crate::{Decode, DecodeAll, Encode};
+
+/// Assert that `value` round-trips through its encoding, and that its encoding is deterministic.
+///
+/// This checks that:
+/// * [`Encode::encoded_size`] and [`Encode::using_encoded`] agree with [`Encode::encode`],
+/// * the encoding decodes back to `value`, consuming all of it,
+/// * encoding the decoded value gives the same bytes.
+///
+/// # Panics
+///
+/// Panics with a description of the first check that failed.
+///
+/// ```
+/// # #[cfg(not(feature="derive"))]
+/// # use parity_scale_codec_derive::{Encode, Decode};
+/// use parity_scale_codec::{Encode, Decode, testing::assert_roundtrip};
+///
+/// #[derive(Debug, PartialEq, Encode, Decode)]
+/// struct Point { x: u32, y: u32 }
+///
+/// assert_roundtrip(Point { x: 1, y: 2 });
+/// assert_roundtrip(vec![Some(1u64), None]);
+/// ```
+pub fn assert_roundtrip<T: Encode + Decode + PartialEq + Debug>(value: T) {
+	let encoded = value.encode();
+	assert_eq!(encoded.len(), value.encoded_size(), "`encoded_size` differs from the encoding length");
+	value.using_encoded(|buf| assert_eq!(buf, &encoded[..], "`using_encoded` differs from `encode`"));
+
+	let decoded = match T::decode_all(&encoded) {
+		Ok(decoded) => decoded,
+		Err(e) => panic!("Decoding {:?} from {:?} failed: {}", value, encoded, e),
+	};
+	assert_eq!(decoded, value, "The decoded value differs from the encoded one");
+	assert_eq!(decoded.encode(), encoded, "Encoding the decoded value gives different bytes");
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{Input, Output, Error};
+
+	#[test]
+	fn roundtrip_works() {
+		assert_roundtrip(5u32);
+		assert_roundtrip((String::from("hello"), vec![1u8, 2], Some(true)));
+	}
+
+	// Decodes one byte less than it encodes.
+	#[derive(Debug, PartialEq)]
+	struct Lossy(u8);
+
+	impl Encode for Lossy {
+		fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+			dest.write(&[self.0, 0]);
+		}
+	}
+
+	impl Decode for Lossy {
+		fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+			input.read_byte().map(Lossy)
+		}
+	}
+
+	#[test]
+	#[should_panic(expected = "Decoding Lossy(1) from [1, 0] failed")]
+	fn roundtrip_requires_all_input_to_be_decoded() {
+		assert_roundtrip(Lossy(1));
+	}
+}
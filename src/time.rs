@@ -0,0 +1,93 @@
+// Copyright 2021 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! `time` specific serialization.
+//!
+//! An `OffsetDateTime` is encoded as its Unix timestamp in nanoseconds (`i128`) followed by its
+//! UTC offset in seconds (`i32`).
+
+use time::{OffsetDateTime, UtcOffset};
+
+use crate::{Encode, Decode, Input, Output, Error};
+use crate::encode_like::EncodeLike;
+
+impl Encode for OffsetDateTime {
+	fn size_hint(&self) -> usize {
+		16 + 4
+	}
+
+	fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+		self.unix_timestamp_nanos().encode_to(dest);
+		self.offset().whole_seconds().encode_to(dest);
+	}
+}
+
+impl EncodeLike for OffsetDateTime {}
+
+impl Decode for OffsetDateTime {
+	fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+		let timestamp = OffsetDateTime::from_unix_timestamp_nanos(i128::decode(input)?)
+			.map_err(|_| "`OffsetDateTime` timestamp out of range")?;
+		let offset = UtcOffset::from_whole_seconds(i32::decode(input)?)
+			.map_err(|_| "`OffsetDateTime` UTC offset out of range")?;
+
+		timestamp.checked_to_offset(offset)
+			.ok_or_else(|| "`OffsetDateTime` out of range in its UTC offset".into())
+	}
+}
+
+#[cfg(feature = "max-encoded-len")]
+impl crate::MaxEncodedLen for OffsetDateTime {
+	fn max_encoded_len() -> usize {
+		16 + 4
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn offset_date_time_roundtrip() {
+		let offset = UtcOffset::from_hms(-5, -30, -15).unwrap();
+		let value = OffsetDateTime::from_unix_timestamp_nanos(1_625_097_600_123_456_789)
+			.unwrap()
+			.to_offset(offset);
+
+		let encoded = value.encode();
+		assert_eq!(encoded.len(), value.size_hint());
+		assert_eq!(encoded, (1_625_097_600_123_456_789i128, -(5 * 3600 + 30 * 60 + 15i32)).encode());
+
+		let decoded = OffsetDateTime::decode(&mut &encoded[..]).unwrap();
+		assert_eq!(decoded, value);
+		assert_eq!(decoded.offset(), offset);
+
+		let utc = OffsetDateTime::UNIX_EPOCH;
+		assert_eq!(OffsetDateTime::decode(&mut &utc.encode()[..]).unwrap().offset(), UtcOffset::UTC);
+	}
+
+	#[test]
+	fn offset_date_time_rejects_invalid_offset() {
+		let encoded = (0i128, 26 * 3600i32).encode();
+		assert!(OffsetDateTime::decode(&mut &encoded[..]).is_err());
+
+		let encoded = (i128::max_value(), 0i32).encode();
+		assert!(OffsetDateTime::decode(&mut &encoded[..]).is_err());
+
+		// The last representable UTC instant can't be shifted to a positive offset.
+		let last = time::PrimitiveDateTime::MAX.assume_utc().unix_timestamp_nanos();
+		assert!(OffsetDateTime::decode(&mut &(last, 0i32).encode()[..]).is_ok());
+		assert!(OffsetDateTime::decode(&mut &(last, 3600i32).encode()[..]).is_err());
+	}
+}
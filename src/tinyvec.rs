@@ -0,0 +1,170 @@
+// Copyright 2021 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! `tinyvec::ArrayVec` and `tinyvec::TinyVec` specific serialization.
+//!
+//! The encoding is the same as for `Vec<T>`. A decoded `TinyVec` is inline if its elements fit
+//! in its array, and on the heap otherwise.
+
+use core::mem;
+
+use tinyvec::{Array, ArrayVec, TinyVec};
+
+use crate::{
+	Encode, Decode, Input, Output, Error, Compact,
+	codec::{compact_encode_len_to, decode_vec_with_len, encode_slice_no_len},
+	encode_like::EncodeLike,
+};
+use crate::alloc::vec::Vec;
+
+/// Decode `len` elements into an `ArrayVec`, `len` being at most its capacity.
+fn decode_array_vec<A: Array + Default, I: Input>(input: &mut I, len: usize) -> Result<ArrayVec<A>, Error>
+	where A::Item: Decode,
+{
+	debug_assert!(len <= A::CAPACITY);
+	let mut result = ArrayVec::new();
+	input.descend_ref()?;
+	for _ in 0..len {
+		result.push(A::Item::decode(input)?);
+	}
+	input.ascend_ref();
+	Ok(result)
+}
+
+impl<A: Array> Encode for ArrayVec<A> where A::Item: Encode {
+	fn size_hint(&self) -> usize {
+		mem::size_of::<u32>() + mem::size_of::<A::Item>() * self.len()
+	}
+
+	fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+		compact_encode_len_to(dest, self.len()).expect("Compact encodes length");
+
+		encode_slice_no_len(&self[..], dest)
+	}
+}
+
+impl<A: Array> EncodeLike for ArrayVec<A> where A::Item: Encode {}
+impl<A: Array, U: Encode> EncodeLike<Vec<U>> for ArrayVec<A> where A::Item: EncodeLike<U> {}
+impl<A: Array, T: EncodeLike<A::Item>> EncodeLike<ArrayVec<A>> for Vec<T> where A::Item: Encode {}
+
+impl<A: Array + Default> Decode for ArrayVec<A> where A::Item: Decode {
+	fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+		let Compact(len) = <Compact<u32>>::decode(input)?;
+		if len as usize > A::CAPACITY {
+			return Err("Attempt to decode a `tinyvec::ArrayVec` with more elements than its capacity".into());
+		}
+
+		decode_array_vec(input, len as usize)
+	}
+}
+
+#[cfg(feature = "max-encoded-len")]
+impl<A: Array> crate::MaxEncodedLen for ArrayVec<A> where A::Item: crate::MaxEncodedLen {
+	fn max_encoded_len() -> usize {
+		use crate::CompactLen;
+		Compact::<u32>::compact_len(&(A::CAPACITY as u32))
+			.saturating_add(A::CAPACITY.saturating_mul(A::Item::max_encoded_len()))
+	}
+}
+
+impl<A: Array> Encode for TinyVec<A> where A::Item: Encode {
+	fn size_hint(&self) -> usize {
+		mem::size_of::<u32>() + mem::size_of::<A::Item>() * self.len()
+	}
+
+	fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+		compact_encode_len_to(dest, self.len()).expect("Compact encodes length");
+
+		encode_slice_no_len(&self[..], dest)
+	}
+}
+
+impl<A: Array> EncodeLike for TinyVec<A> where A::Item: Encode {}
+impl<A: Array, U: Encode> EncodeLike<Vec<U>> for TinyVec<A> where A::Item: EncodeLike<U> {}
+impl<A: Array, T: EncodeLike<A::Item>> EncodeLike<TinyVec<A>> for Vec<T> where A::Item: Encode {}
+
+impl<A: Array + Default> Decode for TinyVec<A> where A::Item: Decode {
+	fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+		let len = <Compact<u32>>::decode(input)?.0 as usize;
+		if len <= A::CAPACITY {
+			decode_array_vec(input, len).map(TinyVec::Inline)
+		} else {
+			decode_vec_with_len(input, len).map(TinyVec::Heap)
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn array_vec_encodes_like_vec() {
+		let mut v = ArrayVec::<[u16; 4]>::new();
+		v.extend_from_slice(&[1, 2, 3]);
+
+		let encoded = v.encode();
+		assert_eq!(encoded, vec![1u16, 2, 3].encode());
+		assert_eq!(ArrayVec::<[u16; 4]>::decode(&mut &encoded[..]), Ok(v));
+	}
+
+	#[test]
+	fn array_vec_rejects_over_capacity() {
+		let encoded = vec![1u8, 2, 3, 4, 5].encode();
+		assert!(ArrayVec::<[u8; 4]>::decode(&mut &encoded[..]).is_err());
+
+		// Only the length is read before rejecting.
+		let encoded = Compact(u32::max_value()).encode();
+		assert!(ArrayVec::<[u32; 16]>::decode(&mut &encoded[..]).is_err());
+	}
+
+	#[test]
+	fn tiny_vec_inline_and_heap_round_trip() {
+		let inline: TinyVec<[u32; 4]> = vec![1u32, 2].into_iter().collect();
+		assert!(inline.is_inline());
+		let encoded = inline.encode();
+		assert_eq!(encoded, vec![1u32, 2].encode());
+		let decoded = TinyVec::<[u32; 4]>::decode(&mut &encoded[..]).unwrap();
+		assert!(decoded.is_inline());
+		assert_eq!(decoded, inline);
+
+		let heap: TinyVec<[u32; 4]> = (0..10u32).collect();
+		assert!(heap.is_heap());
+		let encoded = heap.encode();
+		assert_eq!(encoded, (0..10u32).collect::<Vec<_>>().encode());
+		let decoded = TinyVec::<[u32; 4]>::decode(&mut &encoded[..]).unwrap();
+		assert!(decoded.is_heap());
+		assert_eq!(decoded, heap);
+
+		// A value moved to the heap and shrunk back is decoded inline.
+		let mut shrunk = heap;
+		shrunk.truncate(3);
+		let decoded = TinyVec::<[u32; 4]>::decode(&mut &shrunk.encode()[..]).unwrap();
+		assert!(decoded.is_inline());
+		assert_eq!(decoded, shrunk);
+
+		let encoded = Compact(u32::max_value()).encode();
+		assert!(TinyVec::<[u32; 4]>::decode(&mut &encoded[..]).is_err());
+	}
+
+	#[test]
+	#[cfg(feature = "max-encoded-len")]
+	fn array_vec_max_encoded_len() {
+		use crate::MaxEncodedLen;
+
+		let v: ArrayVec<[u32; 64]> = core::iter::repeat(u32::max_value()).take(64).collect();
+		assert_eq!(v.encode().len(), ArrayVec::<[u32; 64]>::max_encoded_len());
+		assert_eq!(ArrayVec::<[u32; 64]>::max_encoded_len(), 2 + 64 * 4);
+	}
+}
@@ -0,0 +1,73 @@
+// Copyright 2021 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for the records emitted by `DebugInput`.
+#![cfg(feature = "debug-input")]
+
+use std::sync::Mutex;
+
+use parity_scale_codec::{DebugInput, Decode, Encode, DEBUG_INPUT_LOG_TARGET};
+#[cfg(not(feature = "derive"))]
+use parity_scale_codec_derive::{Encode, Decode};
+
+struct CaptureLogger(Mutex<Vec<String>>);
+
+impl log::Log for CaptureLogger {
+	fn enabled(&self, metadata: &log::Metadata) -> bool {
+		metadata.target() == DEBUG_INPUT_LOG_TARGET
+	}
+
+	fn log(&self, record: &log::Record) {
+		if self.enabled(record.metadata()) {
+			self.0.lock().unwrap().push(record.args().to_string());
+		}
+	}
+
+	fn flush(&self) {}
+}
+
+static LOGGER: CaptureLogger = CaptureLogger(Mutex::new(Vec::new()));
+
+#[derive(Encode, Decode, Debug, PartialEq)]
+struct Simple {
+	a: u32,
+	b: u16,
+	c: Box<u8>,
+}
+
+#[test]
+fn trace_follows_decoded_fields() {
+	log::set_logger(&LOGGER).unwrap();
+	log::set_max_level(log::LevelFilter::Trace);
+
+	let value = Simple { a: 5, b: 0x0102, c: Box::new(7) };
+	let encoded = value.encode();
+
+	let mut input = DebugInput::new(&encoded[..]);
+	assert_eq!(Simple::decode(&mut input), Ok(value));
+	assert_eq!(input.offset(), encoded.len());
+	assert!(u8::decode(&mut input).is_err());
+
+	assert_eq!(
+		*LOGGER.0.lock().unwrap(),
+		vec![
+			"read 4 bytes at offset 0: [05, 00, 00, 00]",
+			"read 2 bytes at offset 4: [02, 01]",
+			"descend at offset 6",
+			"read 1 bytes at offset 6: [07]",
+			"ascend at offset 7",
+			"failed to read 1 bytes at offset 7: Not enough data to fill buffer",
+		],
+	);
+}
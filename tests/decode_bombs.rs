@@ -0,0 +1,310 @@
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Adversarial inputs fed to the built-in `Decode` implementations.
+//!
+//! Decoding a few bytes must fail with an error: it must neither panic nor allocate memory
+//! according to a length prefix that the input can't back.
+//!
+//! Collections of zero sized types are not checked: any length is a valid encoding for them, as
+//! their elements take no bytes. Recursive types are only safe with `DecodeLimit`.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet, LinkedList, VecDeque};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use parity_scale_codec::{
+	Compact, Decode, DecodeLimit, Encode, FixedLenVec, OptionBool, PackedBoolVec,
+};
+#[cfg(not(feature = "derive"))]
+use parity_scale_codec_derive::{Decode, Encode};
+
+/// The largest single allocation accepted while decoding an adversarial input.
+const MAX_ALLOCATION: usize = 64 * 1024;
+
+/// Allocator recording the largest allocation of the current thread while armed.
+struct TrackingAllocator;
+
+thread_local! {
+	static TRACKING: Cell<bool> = Cell::new(false);
+	static LARGEST: Cell<usize> = Cell::new(0);
+}
+
+fn record(size: usize) {
+	// `try_with` as the thread locals may already be destroyed at thread exit.
+	let _ = TRACKING.try_with(|tracking| {
+		if tracking.get() {
+			let _ = LARGEST.try_with(|largest| largest.set(largest.get().max(size)));
+		}
+	});
+}
+
+unsafe impl GlobalAlloc for TrackingAllocator {
+	unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+		record(layout.size());
+		System.alloc(layout)
+	}
+
+	unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+		record(layout.size());
+		System.alloc_zeroed(layout)
+	}
+
+	unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+		record(new_size);
+		System.realloc(ptr, layout, new_size)
+	}
+
+	unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+		System.dealloc(ptr, layout)
+	}
+}
+
+#[global_allocator]
+static ALLOCATOR: TrackingAllocator = TrackingAllocator;
+
+/// Decode `T` from `input`, returning the result and the largest allocation made meanwhile.
+fn tracked_decode<T: Decode>(input: &[u8]) -> (std::thread::Result<bool>, usize) {
+	LARGEST.with(|largest| largest.set(0));
+	TRACKING.with(|tracking| tracking.set(true));
+	let result = catch_unwind(AssertUnwindSafe(|| T::decode(&mut &input[..]).is_ok()));
+	TRACKING.with(|tracking| tracking.set(false));
+	(result, LARGEST.with(Cell::get))
+}
+
+/// Assert that decoding `T` from `input` fails gracefully.
+fn assert_rejected<T: Decode>(type_name: &str, input: &[u8]) {
+	let (result, largest) = tracked_decode::<T>(input);
+	match result {
+		Ok(false) => (),
+		Ok(true) => panic!("`{}` decoded the adversarial input {:?}", type_name, input),
+		Err(_) => panic!("`{}` panicked on the input {:?}", type_name, input),
+	}
+	assert!(
+		largest <= MAX_ALLOCATION,
+		"`{}` allocated {} bytes decoding the {} bytes input {:?}",
+		type_name, largest, input.len(), input,
+	);
+}
+
+/// Length prefixes announcing far more data than the inputs provide.
+fn huge_prefixes() -> Vec<Vec<u8>> {
+	let mut prefixes = vec![
+		Compact(u32::max_value()).encode(),
+		Compact(u32::max_value() / 2).encode(),
+		Compact(1u32 << 20).encode(),
+		Compact(u64::max_value()).encode(),
+		u32::max_value().encode(),
+	];
+	// The same with a few bytes of data after the prefix.
+	for i in 0..prefixes.len() {
+		let mut with_data = prefixes[i].clone();
+		with_data.extend_from_slice(&[1, 0, 0, 0, 0, 0, 0, 0]);
+		prefixes.push(with_data);
+	}
+	prefixes
+}
+
+/// Check `T` against huge length prefixes, and against all strict prefixes of `valid`.
+fn check<T: Decode + Encode>(type_name: &str, valid: T) {
+	for input in huge_prefixes() {
+		// Some prefixes are valid inputs for some types, e.g. an `u32` or a compact number.
+		if T::decode(&mut &input[..]).map_or(false, |t| t.encode() == input) {
+			continue;
+		}
+		// Fixed size types may decode a prefix of the input, only panics are of interest then.
+		let (result, largest) = tracked_decode::<T>(&input);
+		assert!(result.is_ok(), "`{}` panicked on the input {:?}", type_name, input);
+		assert!(
+			largest <= MAX_ALLOCATION,
+			"`{}` allocated {} bytes decoding the input {:?}", type_name, largest, input,
+		);
+	}
+
+	let encoded = valid.encode();
+	assert!(T::decode(&mut &encoded[..]).is_ok(), "`{}` decodes its own encoding", type_name);
+	for len in 0..encoded.len() {
+		assert_rejected::<T>(type_name, &encoded[..len]);
+	}
+}
+
+macro_rules! check_types {
+	( $( $type:ty => $valid:expr, )* ) => {
+		$( check::<$type>(stringify!($type), $valid); )*
+	}
+}
+
+#[test]
+fn primitives_reject_adversarial_inputs() {
+	check_types! {
+		bool => true,
+		u8 => 1,
+		u16 => 1,
+		u32 => 1,
+		u64 => 1,
+		u128 => 1,
+		i64 => -1,
+		() => (),
+		Compact<u8> => Compact(u8::max_value()),
+		Compact<u16> => Compact(u16::max_value()),
+		Compact<u32> => Compact(u32::max_value()),
+		Compact<u64> => Compact(u64::max_value()),
+		Compact<u128> => Compact(u128::max_value()),
+		OptionBool => OptionBool(Some(true)),
+		Option<u32> => Some(7),
+		Result<u32, u64> => Err(7),
+		Box<u64> => Box::new(7),
+		(u8, u32, Compact<u64>) => (1, 2, Compact(3)),
+		[u8; 32] => [7; 32],
+		[u32; 1024] => [7; 1024],
+		std::time::Duration => std::time::Duration::from_millis(1500),
+		std::ops::Range<u32> => 1..2,
+	}
+}
+
+#[test]
+fn collections_reject_adversarial_inputs() {
+	check_types! {
+		Vec<u8> => vec![1, 2, 3],
+		Vec<u32> => vec![1, 2, 3],
+		Vec<Vec<u8>> => vec![vec![1], vec![2, 3]],
+		Vec<String> => vec!["a".into(), "bc".into()],
+		Vec<Option<u64>> => vec![None, Some(1)],
+		Vec<[u8; 1024]> => vec![[1; 1024]],
+		String => "hello".into(),
+		Box<[u16]> => vec![1, 2].into_boxed_slice(),
+		Box<str> => "hello".into(),
+		VecDeque<u64> => vec![1, 2].into_iter().collect(),
+		LinkedList<u32> => vec![1, 2].into_iter().collect(),
+		BinaryHeap<u32> => vec![1, 2].into_iter().collect(),
+		BTreeSet<u32> => vec![1, 2].into_iter().collect(),
+		BTreeMap<u32, Vec<u8>> => vec![(1, vec![2])].into_iter().collect(),
+		HashSet<u32> => vec![1, 2].into_iter().collect(),
+		HashMap<u32, u64> => vec![(1, 2)].into_iter().collect(),
+		FixedLenVec<u32> => FixedLenVec(vec![1, 2]),
+		PackedBoolVec => PackedBoolVec(vec![true; 9]),
+		std::ffi::CString => std::ffi::CString::new("hello").unwrap(),
+	}
+}
+
+#[test]
+#[cfg(feature = "bit-vec")]
+fn bit_vecs_reject_adversarial_inputs() {
+	use bitvec::{vec::BitVec, order::{Lsb0, Msb0}};
+
+	check_types! {
+		BitVec<u8, Lsb0> => [true, false, true].iter().copied().collect(),
+		BitVec<u16, Msb0> => [true; 17].iter().copied().collect(),
+		BitVec<u32, Lsb0> => [true; 33].iter().copied().collect(),
+		BitVec<u64, Msb0> => [true; 65].iter().copied().collect(),
+		BitVec<usize, Lsb0> => [true; 65].iter().copied().collect(),
+		bitvec::boxed::BitBox<u8, Msb0> => [true; 9].iter().copied().collect(),
+		bitvec::array::BitArray<[u16; 2], Lsb0> => bitvec::array::BitArray::new([1, 2]),
+	}
+}
+
+#[test]
+fn wrappers_reject_adversarial_inputs() {
+	use std::num::NonZeroU32;
+	use std::net::{Ipv6Addr, SocketAddr, SocketAddrV6};
+	use parity_scale_codec::{CompactDuration, ExitCode, OptionNonZero, PackedBools, Seconds};
+
+	check_types! {
+		OptionNonZero<NonZeroU32> => OptionNonZero(NonZeroU32::new(7)),
+		PackedBools<9> => PackedBools([true; 9]),
+		Compact<Seconds> => Compact(Seconds::new(1 << 40)),
+		CompactDuration => CompactDuration(std::time::Duration::new(1 << 40, 7)),
+		ExitCode => ExitCode(-1),
+		SocketAddr => SocketAddr::V6(SocketAddrV6::new(Ipv6Addr::LOCALHOST, 80, 1, 2)),
+	}
+}
+
+#[test]
+#[cfg(feature = "heapless")]
+fn heapless_rejects_adversarial_inputs() {
+	check_types! {
+		heapless::Vec<u32, 8> => heapless::Vec::from_slice(&[1, 2, 3]).unwrap(),
+	}
+}
+
+#[test]
+#[cfg(feature = "tinyvec")]
+fn tinyvec_rejects_adversarial_inputs() {
+	check_types! {
+		tinyvec::ArrayVec<[u32; 8]> => [1, 2, 3].iter().copied().collect(),
+		tinyvec::TinyVec<[u64; 2]> => (0..5).collect(),
+	}
+}
+
+#[test]
+#[cfg(feature = "bigint")]
+fn bigints_reject_adversarial_inputs() {
+	use num_bigint::{BigInt, BigUint};
+
+	check_types! {
+		BigUint => BigUint::from(u128::max_value()),
+		BigInt => BigInt::from(i128::min_value()),
+	}
+}
+
+#[test]
+#[cfg(feature = "bytes")]
+fn bytes_reject_adversarial_inputs() {
+	check_types! {
+		bytes::Bytes => bytes::Bytes::from_static(b"hello"),
+		bytes::BytesMut => bytes::BytesMut::from(&b"hello"[..]),
+	}
+}
+
+#[test]
+#[cfg(feature = "generic-array")]
+fn generic_arrays_reject_adversarial_inputs() {
+	use generic_array::{arr, GenericArray, typenum::U3};
+
+	check_types! {
+		GenericArray<u32, U3> => arr![u32; 1, 2, 3],
+	}
+}
+
+#[derive(Encode, Decode)]
+enum Nested {
+	Leaf,
+	Node(Box<Nested>),
+	Some(Option<Box<Nested>>),
+}
+
+#[test]
+fn deep_nesting_is_rejected_by_the_depth_limit() {
+	// A million nested `Node`s, which would overflow the stack without a depth limit.
+	let mut input = vec![1u8; 1_000_000];
+	input.push(0);
+	assert!(Nested::decode_with_depth_limit(64, &input).is_err());
+
+	// Same through `Option`s.
+	let mut input = [2u8, 1].repeat(500_000);
+	input.push(0);
+	assert!(Nested::decode_with_depth_limit(64, &input).is_err());
+
+	// Nested collections.
+	let nested = vec![vec![vec![vec![1u8]]]];
+	assert!(<Vec<Vec<Vec<Vec<u8>>>>>::decode_with_depth_limit(2, &nested.encode()).is_err());
+	assert!(<Vec<Vec<Vec<Vec<u8>>>>>::decode_with_depth_limit(4, &nested.encode()).is_ok());
+
+	// Truncated deep inputs.
+	let mut input = vec![1u8; 100];
+	input.push(1);
+	assert_rejected::<Nested>("Nested", &input);
+}
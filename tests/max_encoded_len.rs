@@ -180,3 +180,227 @@ enum EnumMaxNotSum {
 fn enum_max_not_sum_max_length() {
 	assert_eq!(EnumMaxNotSum::max_encoded_len(), 1 + u32::max_encoded_len());
 }
+
+#[derive(Encode, MaxEncodedLen)]
+struct WithInfallible {
+	res: Result<u32, std::convert::Infallible>,
+}
+
+#[test]
+fn infallible_max_length() {
+	assert_eq!(WithInfallible::max_encoded_len(), 1 + u32::max_encoded_len());
+}
+
+#[derive(Encode, MaxEncodedLen)]
+struct WithPhantomPinned {
+	value: u32,
+	_pin: std::marker::PhantomPinned,
+}
+
+#[test]
+fn phantom_pinned_max_length() {
+	assert_eq!(WithPhantomPinned::max_encoded_len(), u32::max_encoded_len());
+}
+
+#[test]
+fn atomic_max_length() {
+	use std::sync::atomic::{AtomicBool, AtomicU64};
+
+	assert_eq!(AtomicBool::max_encoded_len(), 1);
+	assert_eq!(AtomicU64::max_encoded_len(), 8);
+}
+
+#[derive(Encode, MaxEncodedLen)]
+struct CompactField {
+	#[codec(compact)]
+	n: u64,
+	m: u64,
+}
+
+#[test]
+fn compact_field_max_length() {
+	assert_eq!(CompactField::max_encoded_len(), 9 + 8);
+	assert_eq!(
+		CompactField { n: u64::max_value(), m: u64::max_value() }.encode().len(),
+		CompactField::max_encoded_len(),
+	);
+}
+
+#[derive(Encode, MaxEncodedLen)]
+enum CompactVariant {
+	A(#[codec(compact)] u128),
+	B { #[codec(compact)] n: u32 },
+}
+
+#[test]
+fn compact_variant_max_length() {
+	assert_eq!(CompactVariant::max_encoded_len(), 1 + 17);
+	assert_eq!(CompactVariant::A(u128::max_value()).encode().len(), CompactVariant::max_encoded_len());
+	assert_eq!(CompactVariant::B { n: u32::max_value() }.encode().len(), 1 + 5);
+}
+
+#[derive(Encode, MaxEncodedLen)]
+enum WithUnknown {
+	A(u16),
+	#[codec(unknown)]
+	Unknown(u8),
+}
+
+#[test]
+fn unknown_variant_max_length() {
+	assert_eq!(WithUnknown::max_encoded_len(), 1 + 2);
+	assert_eq!(WithUnknown::Unknown(7).encode().len(), 1);
+}
+
+#[test]
+fn wrapping_max_length() {
+	assert_eq!(std::num::Wrapping::<i64>::max_encoded_len(), 8);
+}
+
+#[test]
+fn control_flow_max_length() {
+	use std::ops::ControlFlow;
+
+	assert_eq!(ControlFlow::<u16, u64>::max_encoded_len(), 1 + 8);
+	assert_eq!(ControlFlow::<u64, ()>::max_encoded_len(), 1 + 8);
+	assert_eq!(ControlFlow::<u16, u64>::Continue(0).encode().len(), ControlFlow::<u16, u64>::max_encoded_len());
+}
+
+#[derive(Encode, MaxEncodedLen)]
+struct Padded {
+	#[codec(pad = 4)]
+	a: u8,
+	b: u16,
+}
+
+#[test]
+fn padded_field_max_length() {
+	assert_eq!(Padded::max_encoded_len(), 1 + 3 + 2);
+	assert_eq!(Padded { a: 0, b: 0 }.encode().len(), Padded::max_encoded_len());
+}
+
+#[test]
+fn nested_option_max_length() {
+	assert_eq!(Option::<Option<u32>>::max_encoded_len(), 1 + 1 + 4);
+	assert_eq!(Some(Some(0u32)).encode().len(), Option::<Option<u32>>::max_encoded_len());
+}
+
+#[test]
+fn range_from_to_full_max_length() {
+	use std::ops::{RangeFrom, RangeFull, RangeTo, RangeToInclusive};
+
+	assert_eq!(RangeFrom::<u32>::max_encoded_len(), 4);
+	assert_eq!(RangeTo::<u64>::max_encoded_len(), 8);
+	assert_eq!(RangeToInclusive::<u16>::max_encoded_len(), 2);
+	assert_eq!(RangeFull::max_encoded_len(), 0);
+	assert_eq!((5u32..).encode().len(), RangeFrom::<u32>::max_encoded_len());
+}
+
+#[test]
+fn option_non_zero_max_length() {
+	use std::num::{NonZeroU32, NonZeroU64};
+	use parity_scale_codec::OptionNonZero;
+
+	assert_eq!(OptionNonZero::<NonZeroU32>::max_encoded_len(), 4);
+	assert_eq!(OptionNonZero::<NonZeroU64>::max_encoded_len(), 8);
+	assert_eq!(OptionNonZero::<NonZeroU32>(None).encode().len(), 4);
+}
+
+#[derive(Encode, MaxEncodedLen)]
+#[codec(transparent)]
+struct Transparent<T> {
+	#[codec(skip)]
+	_skipped: u32,
+	inner: T,
+}
+
+#[test]
+fn transparent_max_length() {
+	assert_eq!(Transparent::<u64>::max_encoded_len(), u64::max_encoded_len());
+	assert_eq!(Transparent::<Compact<u32>>::max_encoded_len(), 5);
+}
+
+#[test]
+fn array_max_length() {
+	assert_eq!(<[u8; 32]>::max_encoded_len(), 32);
+	assert_eq!(<[u32; 8]>::max_encoded_len(), 8 * 4);
+	assert_eq!(<[Compact<u64>; 2]>::max_encoded_len(), 2 * 9);
+	assert_eq!(<[u16; 0]>::max_encoded_len(), 0);
+	assert_eq!([0u8; 32].encode().len(), <[u8; 32]>::max_encoded_len());
+}
+
+/// Encodes a `u32` as a single byte, saturating.
+mod saturating_u8 {
+	use parity_scale_codec::{Decode, Error, Input, Output};
+
+	pub fn encode<W: Output + ?Sized>(value: &u32, dest: &mut W) {
+		dest.push_byte((*value).min(u8::max_value() as u32) as u8);
+	}
+
+	pub fn decode<I: Input>(input: &mut I) -> Result<u32, Error> {
+		u8::decode(input).map(Into::into)
+	}
+
+	pub fn max_encoded_len() -> usize {
+		1
+	}
+}
+
+#[derive(Encode, MaxEncodedLen)]
+struct WithModule {
+	#[codec(with = "saturating_u8")]
+	level: u32,
+	other: u32,
+}
+
+#[test]
+fn with_module_max_length() {
+	assert_eq!(WithModule::max_encoded_len(), 1 + 4);
+	assert_eq!(WithModule { level: 1000, other: 0 }.encode(), vec![255, 0, 0, 0, 0]);
+}
+
+/// Neither `Encode` nor `MaxEncodedLen`.
+struct NotMel;
+
+#[derive(Encode, MaxEncodedLen)]
+struct WithPhantom<T, U> {
+	value: U,
+	_marker: std::marker::PhantomData<T>,
+	_other: core::marker::PhantomData<(T, U)>,
+}
+
+#[derive(Encode, MaxEncodedLen)]
+enum EnumWithPhantom<T> {
+	A(u16, std::marker::PhantomData<T>),
+	B,
+}
+
+#[test]
+fn phantom_data_is_not_counted_nor_bounded() {
+	assert_eq!(WithPhantom::<NotMel, u32>::max_encoded_len(), 4);
+	assert_eq!(EnumWithPhantom::<NotMel>::max_encoded_len(), 1 + 2);
+}
+
+#[derive(Encode, MaxEncodedLen)]
+struct WithSkipped<T> {
+	value: u8,
+	#[codec(skip)]
+	_skipped: Option<T>,
+}
+
+#[test]
+fn skipped_fields_are_not_counted_nor_bounded() {
+	assert_eq!(WithSkipped::<NotMel>::max_encoded_len(), 1);
+	assert_eq!(WithSkipped::<u64>::max_encoded_len(), 1);
+}
+
+#[test]
+fn tuple_max_length_is_the_sum_of_its_elements() {
+	assert_eq!(<(u8, u32, bool)>::max_encoded_len(), 1 + 4 + 1);
+	assert_eq!((7u8, u32::max_value(), true).encode().len(), <(u8, u32, bool)>::max_encoded_len());
+	assert_eq!(<((u8, Compact<u32>), Option<u16>)>::max_encoded_len(), 1 + 5 + 3);
+	assert_eq!(<()>::max_encoded_len(), 0);
+
+	type Largest = (u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u64);
+	assert_eq!(Largest::max_encoded_len(), 17 + 8);
+}
@@ -18,8 +18,25 @@
 fn derive_no_bound_ui() {
 	// As trybuild is using `cargo check`, we don't need the real WASM binaries.
 	std::env::set_var("SKIP_WASM_BUILD", "1");
+	alias_trybuild_cargo_config();
 
 	let t = trybuild::TestCases::new();
 	t.compile_fail("tests/max_encoded_len_ui/*.rs");
 	t.pass("tests/max_encoded_len_ui/pass/*.rs");
 }
+
+/// trybuild writes the project config to `.cargo/config`, which recent cargo versions warn about
+/// in the output of every test. Alias it as `config.toml`, so that the warning goes away.
+#[cfg(feature = "derive")]
+fn alias_trybuild_cargo_config() {
+	let target_dir = std::env::var_os("CARGO_TARGET_DIR")
+		.map(std::path::PathBuf::from)
+		.unwrap_or_else(|| std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("target"));
+	let cargo_dir = target_dir.join("tests").join(env!("CARGO_PKG_NAME")).join(".cargo");
+	std::fs::create_dir_all(&cargo_dir).unwrap();
+
+	#[cfg(unix)]
+	if cargo_dir.join("config.toml").symlink_metadata().is_err() {
+		std::os::unix::fs::symlink("config", cargo_dir.join("config.toml")).unwrap();
+	}
+}
@@ -0,0 +1,14 @@
+use parity_scale_codec::{Encode, MaxEncodedLen};
+
+#[derive(Encode)]
+struct NotMel;
+
+#[derive(Encode, MaxEncodedLen)]
+struct Example {
+	size: u32,
+	not_mel: NotMel,
+}
+
+fn main() {
+	let _ = Example::max_encoded_len();
+}
@@ -0,0 +1,10 @@
+use parity_scale_codec::{Encode, MaxEncodedLen};
+
+#[derive(Encode, MaxEncodedLen)]
+#[codec(mel_bound(T: Encode))]
+struct Example<T> {
+	size: u32,
+	not_mel: T,
+}
+
+fn main() {}
@@ -177,7 +177,7 @@ fn should_work_for_unit() {
 	let v = Unit;
 
 	v.using_encoded(|ref slice| {
-		assert_eq!(slice, &[]);
+		assert_eq!(slice, &[0u8; 0]);
 	});
 
 	let mut a: &[u8] = &[];
@@ -487,6 +487,22 @@ fn encode_decode_empty_enum() {
 	);
 }
 
+#[test]
+fn empty_enum_as_generic_parameter() {
+	#[derive(Encode, Decode, PartialEq, Debug)]
+	enum Never {}
+
+	#[derive(Encode, Decode, PartialEq, Debug)]
+	struct Generic<T> {
+		value: Result<u8, T>,
+	}
+
+	let value = Generic::<Never> { value: Ok(3) };
+	assert_eq!(value.encode(), vec![0, 3]);
+	assert_eq!(Generic::<Never>::decode(&mut &[0, 3][..]), Ok(value));
+	assert!(Generic::<Never>::decode(&mut &[1, 0][..]).is_err());
+}
+
 #[test]
 fn codec_vec_u8() {
 	for v in [
@@ -586,3 +602,308 @@ fn custom_trait_bound() {
 
 	Something::<NotEncode, u32>::decode(&mut &encoded[..]).unwrap();
 }
+
+#[test]
+fn infallible_field_derives() {
+	use std::convert::Infallible;
+
+	#[derive(Debug, PartialEq, Encode, Decode)]
+	struct WithInfallible<T> {
+		res: Result<T, Infallible>,
+	}
+
+	let value = WithInfallible::<u32> { res: Ok(42) };
+	let encoded = value.encode();
+	assert_eq!(encoded, vec![0, 42, 0, 0, 0]);
+	assert_eq!(WithInfallible::<u32>::decode(&mut &encoded[..]).unwrap(), value);
+	assert!(WithInfallible::<u32>::decode(&mut &[1][..]).is_err());
+}
+
+#[test]
+fn phantom_pinned_field_derives() {
+	use std::marker::PhantomPinned;
+
+	#[derive(Debug, PartialEq, Encode, Decode)]
+	struct Pinned {
+		value: u16,
+		_pin: PhantomPinned,
+	}
+
+	let value = Pinned { value: 7, _pin: PhantomPinned };
+	let encoded = value.encode();
+	assert_eq!(encoded, 7u16.encode());
+	assert_eq!(Pinned::decode(&mut &encoded[..]).unwrap(), value);
+}
+
+#[test]
+fn validate_attribute_rejects_invalid_values() {
+	fn check_range(range: &Range) -> Result<(), &'static str> {
+		if range.start <= range.end {
+			Ok(())
+		} else {
+			Err("`start` is bigger than `end`")
+		}
+	}
+
+	#[derive(Debug, PartialEq, Encode, Decode)]
+	#[codec(validate = "check_range")]
+	struct Range {
+		start: u32,
+		end: u32,
+	}
+
+	let valid = Range { start: 1, end: 2 };
+	assert_eq!(Range::decode(&mut &valid.encode()[..]), Ok(valid));
+
+	let invalid = Range { start: 2, end: 1 };
+	assert_eq!(
+		Range::decode(&mut &invalid.encode()[..]).err().unwrap().to_string(),
+		"Could not validate `Range`:\n\t`start` is bigger than `end`\n",
+	);
+}
+
+#[test]
+fn validate_attribute_works_on_enums() {
+	fn not_zero(value: &MaybeZero) -> Result<(), Error> {
+		match value {
+			MaybeZero::Value(0) => Err("zero".into()),
+			_ => Ok(()),
+		}
+	}
+
+	#[derive(Debug, PartialEq, Encode, Decode)]
+	#[codec(validate = "not_zero")]
+	enum MaybeZero {
+		Nothing,
+		Value(u8),
+	}
+
+	assert_eq!(MaybeZero::decode(&mut &[0][..]), Ok(MaybeZero::Nothing));
+	assert_eq!(MaybeZero::decode(&mut &[1, 5][..]), Ok(MaybeZero::Value(5)));
+	assert!(MaybeZero::decode(&mut &[1, 0][..]).is_err());
+}
+
+#[test]
+fn chained_input_decodes_struct_across_segments() {
+	#[derive(Debug, PartialEq, Encode, Decode)]
+	struct Payload {
+		id: u64,
+		#[codec(compact)]
+		len: u32,
+		data: Vec<u8>,
+	}
+
+	let payload = Payload { id: 0x0102_0304_0506_0708, len: 1 << 20, data: vec![9; 40] };
+	let encoded = payload.encode();
+
+	// `id` straddles the first boundary, `len` the second and `data` the third.
+	let segments = [&encoded[..3], &encoded[3..10], &encoded[10..20], &encoded[20..]];
+	let mut input = parity_scale_codec::ChainedInput::new(&segments);
+	assert_eq!(Payload::decode(&mut input), Ok(payload));
+}
+
+#[test]
+fn unknown_variant_is_decoding_fallback() {
+	#[derive(Debug, PartialEq, Encode, Decode)]
+	enum Message {
+		Ping,
+		Data(u32),
+		#[codec(unknown)]
+		Unknown,
+	}
+
+	assert_eq!(Message::decode(&mut &[0][..]), Ok(Message::Ping));
+	assert_eq!(Message::decode(&mut &[1, 5, 0, 0, 0][..]), Ok(Message::Data(5)));
+	assert_eq!(Message::decode(&mut &[2][..]), Ok(Message::Unknown));
+	assert_eq!(Message::decode(&mut &[200][..]), Ok(Message::Unknown));
+
+	assert_eq!(Message::Unknown.encode(), vec![2]);
+	assert!(Message::decode(&mut &[][..]).is_err());
+}
+
+#[test]
+fn unknown_variant_captures_discriminant() {
+	#[derive(Debug, PartialEq, Encode, Decode)]
+	enum Message {
+		Ping,
+		#[codec(unknown)]
+		Unknown(u8),
+		#[codec(index = 5)]
+		Data(u32),
+	}
+
+	assert_eq!(Message::decode(&mut &[0][..]), Ok(Message::Ping));
+	assert_eq!(Message::decode(&mut &[5, 1, 0, 0, 0][..]), Ok(Message::Data(1)));
+	assert_eq!(Message::decode(&mut &[1][..]), Ok(Message::Unknown(1)));
+	assert_eq!(Message::decode(&mut &[250, 1, 2][..]), Ok(Message::Unknown(250)));
+
+	for tag in &[1u8, 42, 255] {
+		let encoded = Message::Unknown(*tag).encode();
+		assert_eq!(encoded, vec![*tag]);
+		assert_eq!(Message::decode(&mut &encoded[..]), Ok(Message::Unknown(*tag)));
+	}
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+enum Versioned {
+	Ping,
+	Data(u32),
+	#[codec(unknown_with = "Versioned::Unsupported(Vec::new())")]
+	Unsupported(Vec<u8>),
+}
+
+#[test]
+fn unknown_with_variant_reads_only_the_index() {
+	assert_eq!(Versioned::decode(&mut &[1, 5, 0, 0, 0][..]), Ok(Versioned::Data(5)));
+
+	// The variant itself keeps its index and payload.
+	let unsupported = Versioned::Unsupported(vec![1, 2]);
+	assert_eq!(unsupported.encode(), vec![2, 2 << 2, 1, 2]);
+	assert_eq!(Versioned::decode(&mut &unsupported.encode()[..]), Ok(unsupported));
+
+	// Without length prefix, the payload of an unknown variant is left in the input.
+	let mut input = &[7, 1, 2, 3][..];
+	assert_eq!(Versioned::decode(&mut input), Ok(Versioned::Unsupported(Vec::new())));
+	assert_eq!(input, &[1, 2, 3]);
+
+	assert!(Versioned::decode(&mut &[][..]).is_err());
+}
+
+#[test]
+fn unknown_with_variant_in_length_prefixed_frames() {
+	// A newer peer sends a variant `9` with a `u64` payload, between two known messages.
+	let frames = vec![
+		Versioned::Ping.encode(),
+		(9u8, 42u64).encode(),
+		Versioned::Data(3).encode(),
+	];
+	let stream = frames.encode();
+
+	let mut input = &stream[..];
+	let frames = <Vec<Vec<u8>>>::decode(&mut input).unwrap();
+	let messages = frames.iter()
+		.map(|frame| Versioned::decode(&mut &frame[..]))
+		.collect::<Result<Vec<_>, _>>();
+	assert_eq!(
+		messages,
+		Ok(vec![Versioned::Ping, Versioned::Unsupported(Vec::new()), Versioned::Data(3)]),
+	);
+	assert!(input.is_empty());
+}
+
+#[test]
+fn pad_attribute_aligns_following_fields() {
+	#[derive(Debug, PartialEq, Encode, Decode)]
+	struct Header {
+		#[codec(pad = 4)]
+		kind: u8,
+		#[codec(pad = 4)]
+		flags: u16,
+		len: u32,
+		#[codec(pad = 4)]
+		tag: u8,
+	}
+
+	let header = Header { kind: 1, flags: 0x0302, len: 0x0706_0504, tag: 8 };
+	let encoded = header.encode();
+	assert_eq!(encoded, vec![1, 0, 0, 0, 2, 3, 0, 0, 4, 5, 6, 7, 8, 0, 0, 0]);
+	assert_eq!(Header::decode(&mut &encoded[..]), Ok(header));
+
+	// Padding bytes are skipped whatever their value, but must be present.
+	let garbage = [1, 0xff, 0xff, 0xff, 2, 3, 0xff, 0xff, 4, 5, 6, 7, 8, 0xff, 0xff, 0xff];
+	assert_eq!(Header::decode(&mut &garbage[..]).unwrap().tag, 8);
+	assert!(Header::decode(&mut &encoded[..14]).is_err());
+}
+
+#[test]
+fn pad_attribute_is_relative_to_variant_fields() {
+	#[derive(Debug, PartialEq, Encode, Decode)]
+	enum Message {
+		Ping(#[codec(pad = 8)] u8, u8),
+		Data {
+			#[codec(pad = 2)]
+			#[codec(compact)]
+			len: u32,
+			#[codec(pad = 4)]
+			data: Vec<u8>,
+		},
+	}
+
+	let ping = Message::Ping(1, 2);
+	assert_eq!(ping.encode(), vec![0, 1, 0, 0, 0, 0, 0, 0, 0, 2]);
+	assert_eq!(Message::decode(&mut &ping.encode()[..]), Ok(ping));
+
+	let data = Message::Data { len: 1, data: vec![5, 6, 7] };
+	assert_eq!(data.encode(), vec![1, 4, 0, 12, 5, 6, 7, 0, 0]);
+	assert_eq!(Message::decode(&mut &data.encode()[..]), Ok(data));
+}
+
+#[test]
+fn enum_bounds_only_field_types_of_variants() {
+	use std::marker::PhantomData;
+
+	// Not `Encode` nor `Decode`.
+	#[derive(Debug, PartialEq)]
+	struct NotCodec;
+
+	#[derive(Debug, PartialEq, Encode, Decode)]
+	enum E<A, B> {
+		X(A),
+		Y(PhantomData<B>),
+	}
+
+	let x = E::<u32, NotCodec>::X(7);
+	assert_eq!(x.encode(), vec![0, 7, 0, 0, 0]);
+	assert_eq!(E::<u32, NotCodec>::decode(&mut &x.encode()[..]), Ok(x));
+
+	let y = E::<u32, NotCodec>::Y(PhantomData);
+	assert_eq!(y.encode(), vec![1]);
+	assert_eq!(E::<u32, NotCodec>::decode(&mut &[1][..]), Ok(y));
+}
+
+/// Encodes a `u64` as big endian, for the `#[codec(with = "..")]` tests.
+mod be_u64 {
+	use parity_scale_codec::{Error, Input, Output};
+
+	pub fn encode<W: Output + ?Sized>(value: &u64, dest: &mut W) {
+		dest.write(&value.to_be_bytes());
+	}
+
+	pub fn decode<I: Input>(input: &mut I) -> Result<u64, Error> {
+		let mut bytes = [0u8; 8];
+		input.read(&mut bytes)?;
+		Ok(u64::from_be_bytes(bytes))
+	}
+}
+
+#[test]
+fn with_attribute_uses_the_module_functions() {
+	#[derive(Debug, PartialEq, Encode, Decode)]
+	struct Header {
+		version: u8,
+		#[codec(with = "be_u64")]
+		number: u64,
+	}
+
+	#[derive(Debug, PartialEq, Encode, Decode)]
+	struct BlockNumber(#[codec(with = "be_u64")] u64);
+
+	#[derive(Debug, PartialEq, Encode, Decode)]
+	enum Event {
+		Imported(#[codec(with = "be_u64")] u64, u16),
+	}
+
+	let header = Header { version: 1, number: 0x0102 };
+	assert_eq!(header.encode(), vec![1, 0, 0, 0, 0, 0, 0, 1, 2]);
+	assert_eq!(Header::decode(&mut &header.encode()[..]), Ok(header));
+
+	let number = BlockNumber(0x0102_0304);
+	assert_eq!(number.encode(), vec![0, 0, 0, 0, 1, 2, 3, 4]);
+	assert_eq!(BlockNumber::decode(&mut &number.encode()[..]), Ok(number));
+
+	let event = Event::Imported(7, 1);
+	assert_eq!(event.encode(), vec![0, 0, 0, 0, 0, 0, 0, 0, 7, 1, 0]);
+	assert_eq!(Event::decode(&mut &event.encode()[..]), Ok(event));
+
+	assert!(BlockNumber::decode(&mut &[0, 0, 0][..]).is_err());
+}
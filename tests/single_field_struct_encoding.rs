@@ -114,3 +114,36 @@ fn test_encoding() {
 	assert_eq!(s_skip_cas, SSkipcas::decode(&mut s_skip_cas_encoded).unwrap());
 	assert_eq!(uh, Uh::decode(&mut uh_encoded).unwrap());
 }
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[codec(transparent)]
+struct Transparent<T>(T);
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[codec(transparent)]
+struct TransparentSkip {
+	#[codec(skip)]
+	s: u32,
+	x: Vec<u8>,
+}
+
+#[test]
+fn transparent_struct_encodes_like_its_field() {
+	use parity_scale_codec::EncodeLike;
+
+	fn encode_like_vec<T: EncodeLike<Vec<u8>>>(t: &T) -> Vec<u8> {
+		t.encode()
+	}
+
+	let x = vec![1u8, 2, 3];
+	let transparent = Transparent(x.clone());
+	assert_eq!(transparent.encode(), x.encode());
+	assert_eq!(transparent.size_hint(), x.size_hint());
+	assert_eq!(Transparent::<Vec<u8>>::decode(&mut &x.encode()[..]), Ok(transparent));
+
+	let skip = TransparentSkip { s: 0, x: x.clone() };
+	assert_eq!(skip.encode(), x.encode());
+	assert_eq!(skip.size_hint(), x.size_hint());
+	assert_eq!(encode_like_vec(&skip), x.encode());
+	assert_eq!(encode_like_vec(&Transparent(x.clone())), x.encode());
+}
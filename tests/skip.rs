@@ -46,7 +46,7 @@ fn enum_struct_test() {
 	let sn = StructNamed { a: UncodecType, b: 1 };
 	let su = StructUnnamed(UncodecType, 1);
 
-	assert_eq!(ea.encode(), Vec::new());
+	assert_eq!(ea.encode(), Vec::<u8>::new());
 
 	let mut eb_encoded: &[u8] = &eb.encode();
 	let mut ec_encoded: &[u8] = &ec.encode();
@@ -76,3 +76,35 @@ fn skip_enum_struct_inner_variant() {
 	let encoded = Enum::Data { some_named: 1, ignore: Some(1) }.encode();
 	assert_eq!(vec![0, 1, 0, 0, 0], encoded);
 }
+
+#[test]
+fn skipped_variant_index_is_not_decodable() {
+	#[derive(PartialEq, Debug, Encode, Decode)]
+	enum Status {
+		#[codec(index = 0)]
+		Active,
+		// Used to be encoded as `1`.
+		#[codec(skip)]
+		Deprecated,
+		#[codec(index = 2)]
+		Closed(u8),
+	}
+
+	assert_eq!(Status::Deprecated.encode(), Vec::<u8>::new());
+	assert!(Status::decode(&mut &[1][..]).is_err());
+	assert_eq!(Status::decode(&mut &[0][..]), Ok(Status::Active));
+	assert_eq!(Status::decode(&mut &[2, 7][..]), Ok(Status::Closed(7)));
+
+	// Without explicit indices, the following variants take the place of the skipped one.
+	#[derive(PartialEq, Debug, Encode, Decode)]
+	enum Shifted {
+		A,
+		#[codec(skip)]
+		B,
+		C,
+	}
+
+	assert_eq!(Shifted::C.encode(), vec![1]);
+	assert_eq!(Shifted::decode(&mut &[1][..]), Ok(Shifted::C));
+	assert!(Shifted::decode(&mut &[2][..]).is_err());
+}